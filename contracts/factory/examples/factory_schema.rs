@@ -0,0 +1,27 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use cw_lottery_factory::msg::{
+  ExecuteMsg, GetConfigResponse, GetGameResponse, GetGamesResponse, GetLeaderboardResponse, GetPlayerStatsResponse,
+  GetTemplateResponse, InstantiateMsg, MigrateMsg, QueryMsg,
+};
+
+fn main() {
+  let mut out_dir = current_dir().unwrap();
+  out_dir.push("schema");
+  create_dir_all(&out_dir).unwrap();
+  remove_schemas(&out_dir).unwrap();
+
+  export_schema(&schema_for!(InstantiateMsg), &out_dir);
+  export_schema(&schema_for!(ExecuteMsg), &out_dir);
+  export_schema(&schema_for!(QueryMsg), &out_dir);
+  export_schema(&schema_for!(MigrateMsg), &out_dir);
+  export_schema(&schema_for!(GetConfigResponse), &out_dir);
+  export_schema(&schema_for!(GetTemplateResponse), &out_dir);
+  export_schema(&schema_for!(GetGameResponse), &out_dir);
+  export_schema(&schema_for!(GetGamesResponse), &out_dir);
+  export_schema(&schema_for!(GetPlayerStatsResponse), &out_dir);
+  export_schema(&schema_for!(GetLeaderboardResponse), &out_dir);
+}
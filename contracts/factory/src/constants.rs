@@ -0,0 +1,8 @@
+/// ID of the reply to the submessage instantiating a new game.
+pub const CREATE_GAME_REPLY_ID: u64 = 1;
+
+/// Number of items returned by paginated queries when no limit is given.
+pub const DEFAULT_QUERY_LIMIT: u32 = 50;
+
+/// Upper bound on the number of items returned by paginated queries.
+pub const MAX_QUERY_LIMIT: u32 = 200;
@@ -0,0 +1,106 @@
+use crate::constants::CREATE_GAME_REPLY_ID;
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::{execute, query, state};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
+use cw2::set_contract_version;
+
+const CONTRACT_NAME: &str = "crates.io:cw-lottery-factory";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+  set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+  state::initialize(deps, &info, &msg)?;
+  Ok(
+    Response::new()
+      .add_attribute("action", "instantiate")
+      .add_attribute("owner", info.sender)
+      .add_attribute("lottery_code_id", msg.lottery_code_id.to_string()),
+  )
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+  match msg {
+    ExecuteMsg::CreateGame(create_game_msg) => execute::create_game(deps, env, info, create_game_msg),
+    ExecuteMsg::SetTemplate { name, template } => execute::set_template(deps, env, info, name, template),
+    ExecuteMsg::RemoveTemplate { name } => execute::remove_template(deps, env, info, name),
+    ExecuteMsg::UpdateConfig { owner, lottery_code_id } => {
+      execute::update_config(deps, env, info, owner, lottery_code_id)
+    },
+    ExecuteMsg::SyncGame { address } => execute::sync_game(deps, env, info, address),
+    ExecuteMsg::RecordStats { updates } => execute::record_stats(deps, env, info, updates),
+  }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+  deps: DepsMut,
+  env: Env,
+  reply: Reply,
+) -> Result<Response, ContractError> {
+  match reply.id {
+    CREATE_GAME_REPLY_ID => execute::create_game_reply(deps, env, reply),
+    id => Err(ContractError::UnknownReplyId { id }),
+  }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(
+  deps: Deps,
+  _env: Env,
+  msg: QueryMsg,
+) -> StdResult<Binary> {
+  let result = match msg {
+    QueryMsg::GetConfig {} => to_binary(&query::get_config(deps)?),
+    QueryMsg::GetTemplate { name } => to_binary(&query::get_template(deps, name)?),
+    QueryMsg::GetGame { address } => to_binary(&query::get_game(deps, address)?),
+    QueryMsg::GetGamesByOwner {
+      owner,
+      status,
+      start_after,
+      limit,
+    } => to_binary(&query::get_games_by_owner(deps, owner, status, start_after, limit)?),
+    QueryMsg::GetGamesByDenom {
+      denom,
+      status,
+      start_after,
+      limit,
+    } => to_binary(&query::get_games_by_denom(deps, denom, status, start_after, limit)?),
+    QueryMsg::GetGamesByEndTime {
+      status,
+      start_after,
+      limit,
+    } => to_binary(&query::get_games_by_end_time(deps, status, start_after, limit)?),
+    QueryMsg::GetPlayerStats { addr } => to_binary(&query::get_player_stats(deps, addr)?),
+    QueryMsg::GetLeaderboard {
+      asset,
+      start_after,
+      limit,
+    } => to_binary(&query::get_leaderboard(deps, asset, start_after, limit)?),
+  }?;
+  Ok(result)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+  deps: DepsMut,
+  _env: Env,
+  _msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+  set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+  Ok(Response::new().add_attribute("action", "migrate"))
+}
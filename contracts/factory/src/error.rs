@@ -0,0 +1,30 @@
+use cosmwasm_std::{Addr, StdError};
+use cw_utils::ParseReplyError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ContractError {
+  #[error("{0}")]
+  Std(#[from] StdError),
+
+  #[error("{0}")]
+  ParseReply(#[from] ParseReplyError),
+
+  #[error("NotAuthorized")]
+  NotAuthorized {},
+
+  #[error("TemplateNotFound")]
+  TemplateNotFound { name: String },
+
+  #[error("GameNotFound")]
+  GameNotFound { address: Addr },
+
+  #[error("UnsupportedAsset")]
+  UnsupportedAsset { asset: String },
+
+  #[error("InvalidDuration")]
+  InvalidDuration {},
+
+  #[error("UnknownReplyId")]
+  UnknownReplyId { id: u64 },
+}
@@ -0,0 +1,144 @@
+use crate::constants::CREATE_GAME_REPLY_ID;
+use crate::error::ContractError;
+use crate::msg::{CreateGameMsg, Template};
+use crate::state::{games, GameEntry, CONFIG, PENDING_TEMPLATE, TEMPLATES};
+use cosmwasm_std::{attr, to_binary, Api, DepsMut, Env, MessageInfo, Reply, Response, SubMsg, WasmMsg};
+use cw_lottery::asset::Asset;
+use cw_lottery::msg::InstantiateMsg as LotteryInstantiateMsg;
+use cw_lottery::state::GAME;
+use cw_utils::parse_reply_instantiate_data;
+
+/// Instantiate a new lottery from a template, owned by the sender and reporting
+/// player stats to the factory. The game is indexed once its instantiation
+/// succeeds.
+pub fn execute_create_game(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  msg: CreateGameMsg,
+) -> Result<Response, ContractError> {
+  let config = CONFIG.load(deps.storage)?;
+  let template = TEMPLATES
+    .may_load(deps.storage, &msg.template)?
+    .ok_or_else(|| ContractError::TemplateNotFound {
+      name: msg.template.clone(),
+    })?;
+
+  validate_duration(&template, msg.duration_minutes)?;
+  validate_assets(deps.as_ref().api, &template, &msg)?;
+
+  let instantiate_msg = LotteryInstantiateMsg {
+    id: msg.id.clone(),
+    owner: Some(info.sender.clone()),
+    name: msg.name,
+    duration_minutes: msg.duration_minutes,
+    denom: msg.denom,
+    cw20_token_address: msg.cw20_token_address,
+    ticket_price: msg.ticket_price,
+    extra_prices: msg.extra_prices,
+    selection: msg.selection,
+    has_distinct_winners: msg.has_distinct_winners,
+    max_tickets_per_player: msg.max_tickets_per_player,
+    funding_threshold: msg.funding_threshold,
+    royalties: template.royalties,
+    randomness: template.randomness,
+    commitment: msg.commitment,
+    caller_policy: template.caller_policy,
+    auto_start_round: template.auto_start_round,
+    auto_distribute: msg.auto_distribute,
+    jackpot: template.jackpot,
+    claim_deadline: template.claim_deadline,
+    registry: Some(env.contract.address),
+  };
+
+  PENDING_TEMPLATE.save(deps.storage, &msg.template)?;
+
+  let instantiate = WasmMsg::Instantiate {
+    admin: Some(config.owner.into()),
+    code_id: config.lottery_code_id,
+    msg: to_binary(&instantiate_msg)?,
+    funds: vec![],
+    label: format!("lottery-{}", msg.id),
+  };
+
+  Ok(
+    Response::new()
+      .add_submessage(SubMsg::reply_on_success(instantiate, CREATE_GAME_REPLY_ID))
+      .add_attributes(vec![
+        attr("action", "create_game"),
+        attr("template", msg.template),
+        attr("id", msg.id),
+        attr("owner", info.sender),
+      ]),
+  )
+}
+
+/// Index the game instantiated by `create_game`, reading its config back from
+/// the new contract.
+pub fn create_game_reply(
+  deps: DepsMut,
+  env: Env,
+  reply: Reply,
+) -> Result<Response, ContractError> {
+  let instantiated = parse_reply_instantiate_data(reply)?;
+  let address = deps.api.addr_validate(&instantiated.contract_address)?;
+
+  let template = PENDING_TEMPLATE.load(deps.storage)?;
+  PENDING_TEMPLATE.remove(deps.storage);
+
+  let game = GAME.query(&deps.querier, address.clone())?;
+  let entry = GameEntry::new(address.clone(), template, env.block.time, &game);
+  games().save(deps.storage, address.clone(), &entry)?;
+
+  Ok(Response::new().add_attributes(vec![attr("action", "create_game_reply"), attr("game_address", address)]))
+}
+
+/// Ensure the game's duration is within the template's bounds, if it has any.
+fn validate_duration(
+  template: &Template,
+  duration_minutes: Option<u32>,
+) -> Result<(), ContractError> {
+  let is_valid = match (
+    duration_minutes,
+    template.min_duration_minutes,
+    template.max_duration_minutes,
+  ) {
+    (_, None, None) => true,
+    (None, _, _) => false,
+    (Some(duration), min, max) => duration >= min.unwrap_or(0) && duration <= max.unwrap_or(u32::MAX),
+  };
+  if !is_valid {
+    return Err(ContractError::InvalidDuration {});
+  }
+  Ok(())
+}
+
+/// Ensure tickets are only priced in the template's assets, if it restricts
+/// them.
+fn validate_assets(
+  api: &dyn Api,
+  template: &Template,
+  msg: &CreateGameMsg,
+) -> Result<(), ContractError> {
+  let allowed = match &template.assets {
+    Some(allowed) => allowed,
+    None => return Ok(()),
+  };
+  let primary = match &msg.cw20_token_address {
+    Some(address) => Asset::Cw20 {
+      address: api.addr_validate(address.as_str())?,
+    },
+    None => Asset::Native {
+      denom: msg.denom.clone(),
+    },
+  };
+  let prices = std::iter::once(&primary).chain(msg.extra_prices.iter().map(|price| &price.asset));
+  for asset in prices {
+    if !allowed.contains(asset) {
+      return Err(ContractError::UnsupportedAsset {
+        asset: asset.to_string(),
+      });
+    }
+  }
+  Ok(())
+}
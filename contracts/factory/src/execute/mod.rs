@@ -0,0 +1,14 @@
+mod create_game;
+mod record_stats;
+mod remove_template;
+mod set_template;
+mod sync_game;
+mod update_config;
+
+pub use create_game::create_game_reply;
+pub use create_game::execute_create_game as create_game;
+pub use record_stats::execute_record_stats as record_stats;
+pub use remove_template::execute_remove_template as remove_template;
+pub use set_template::execute_set_template as set_template;
+pub use sync_game::execute_sync_game as sync_game;
+pub use update_config::execute_update_config as update_config;
@@ -0,0 +1,97 @@
+use crate::error::ContractError;
+use crate::execute::sync_game::sync;
+use crate::state::{asset_stats, games, AssetStats, PLAYER_STATS};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage, Uint128};
+use cw_lottery::asset::AssetAmount;
+use cw_lottery::msg::StatsUpdate;
+
+/// Add to the lifetime stats of players of a game created by the factory, or
+/// subtract what they were refunded or failed to receive from them. Only
+/// the game itself may report its players' stats. Games also report whenever
+/// their status or round changes, so the game is reindexed as well.
+pub fn execute_record_stats(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  updates: Vec<StatsUpdate>,
+) -> Result<Response, ContractError> {
+  let entry = games()
+    .may_load(deps.storage, info.sender.clone())?
+    .ok_or(ContractError::NotAuthorized {})?;
+  let synced = sync(deps.storage, &deps.querier, &entry)?;
+
+  for update in updates.iter() {
+    PLAYER_STATS.update(deps.storage, &update.player, |stats| -> StdResult<_> {
+      let mut stats = stats.unwrap_or_default();
+      stats.ticket_count += update.ticket_count as u64;
+      stats.win_count += update.win_count as u64;
+      stats.ticket_count = stats.ticket_count.saturating_sub(update.refunded_ticket_count as u64);
+      Ok(stats)
+    })?;
+    for spent in update.spent.iter() {
+      add_to_asset_stats(deps.storage, &update.player, spent, |stats| &mut stats.spent)?;
+    }
+    for won in update.won.iter() {
+      add_to_asset_stats(deps.storage, &update.player, won, |stats| &mut stats.won)?;
+    }
+    for claimed in update.claimed.iter() {
+      add_to_asset_stats(deps.storage, &update.player, claimed, |stats| &mut stats.claimed)?;
+    }
+    for refunded in update.refunded.iter() {
+      subtract_from_asset_stats(deps.storage, &update.player, refunded, |stats| &mut stats.spent)?;
+    }
+    for undelivered in update.undelivered.iter() {
+      subtract_from_asset_stats(deps.storage, &update.player, undelivered, |stats| &mut stats.claimed)?;
+    }
+  }
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "record_stats"),
+    attr("game_address", info.sender),
+    attr("player_count", updates.len().to_string()),
+    attr("status", format!("{:?}", synced.status)),
+  ]))
+}
+
+/// Add an amount to the given field of a player's stats in its asset.
+fn add_to_asset_stats(
+  storage: &mut dyn Storage,
+  player: &Addr,
+  amount: &AssetAmount,
+  field: fn(&mut AssetStats) -> &mut Uint128,
+) -> StdResult<()> {
+  update_asset_stats(storage, player, amount, field, |total, amount| total + amount)
+}
+
+/// Subtract an amount from the given field of a player's stats in its asset,
+/// down to zero.
+fn subtract_from_asset_stats(
+  storage: &mut dyn Storage,
+  player: &Addr,
+  amount: &AssetAmount,
+  field: fn(&mut AssetStats) -> &mut Uint128,
+) -> StdResult<()> {
+  update_asset_stats(storage, player, amount, field, Uint128::saturating_sub)
+}
+
+fn update_asset_stats(
+  storage: &mut dyn Storage,
+  player: &Addr,
+  amount: &AssetAmount,
+  field: fn(&mut AssetStats) -> &mut Uint128,
+  apply: fn(Uint128, Uint128) -> Uint128,
+) -> StdResult<()> {
+  asset_stats().update(storage, (player.clone(), amount.asset.key()), |stats| -> StdResult<_> {
+    let mut stats = stats.unwrap_or_else(|| AssetStats {
+      player: player.clone(),
+      asset: amount.asset.clone(),
+      spent: Uint128::zero(),
+      won: Uint128::zero(),
+      claimed: Uint128::zero(),
+    });
+    let total = field(&mut stats);
+    *total = apply(*total, amount.amount);
+    Ok(stats)
+  })?;
+  Ok(())
+}
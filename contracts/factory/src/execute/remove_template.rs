@@ -0,0 +1,24 @@
+use crate::error::ContractError;
+use crate::state::{CONFIG, TEMPLATES};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Remove a template so that no more games can be created from it.
+pub fn execute_remove_template(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  name: String,
+) -> Result<Response, ContractError> {
+  let config = CONFIG.load(deps.storage)?;
+
+  if info.sender != config.owner {
+    return Err(ContractError::NotAuthorized {});
+  }
+  if !TEMPLATES.has(deps.storage, &name) {
+    return Err(ContractError::TemplateNotFound { name });
+  }
+
+  TEMPLATES.remove(deps.storage, &name);
+
+  Ok(Response::new().add_attributes(vec![attr("action", "remove_template"), attr("name", name)]))
+}
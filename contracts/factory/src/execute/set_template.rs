@@ -0,0 +1,29 @@
+use crate::error::ContractError;
+use crate::msg::Template;
+use crate::state::{CONFIG, TEMPLATES};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Add or replace a template. Games already created from it are unaffected.
+pub fn execute_set_template(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  name: String,
+  template: Template,
+) -> Result<Response, ContractError> {
+  let config = CONFIG.load(deps.storage)?;
+
+  if info.sender != config.owner {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  if let (Some(min), Some(max)) = (template.min_duration_minutes, template.max_duration_minutes) {
+    if min > max {
+      return Err(ContractError::InvalidDuration {});
+    }
+  }
+
+  TEMPLATES.save(deps.storage, &name, &template)?;
+
+  Ok(Response::new().add_attributes(vec![attr("action", "set_template"), attr("name", name)]))
+}
@@ -0,0 +1,39 @@
+use crate::error::ContractError;
+use crate::state::{games, GameEntry};
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, QuerierWrapper, Response, StdResult, Storage};
+use cw_lottery::state::GAME;
+
+/// Refresh the indexed status, round and end time of a game from the game
+/// itself. Anyone may sync any game created by the factory.
+pub fn execute_sync_game(
+  deps: DepsMut,
+  _env: Env,
+  _info: MessageInfo,
+  address: Addr,
+) -> Result<Response, ContractError> {
+  let entry = games()
+    .may_load(deps.storage, address.clone())?
+    .ok_or_else(|| ContractError::GameNotFound {
+      address: address.clone(),
+    })?;
+
+  let synced = sync(deps.storage, &deps.querier, &entry)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "sync_game"),
+    attr("game_address", address),
+    attr("status", format!("{:?}", synced.status)),
+  ]))
+}
+
+/// Reindex a game under its current status, round and end time.
+pub(crate) fn sync(
+  storage: &mut dyn Storage,
+  querier: &QuerierWrapper,
+  entry: &GameEntry,
+) -> StdResult<GameEntry> {
+  let game = GAME.query(querier, entry.address.clone())?;
+  let synced = GameEntry::new(entry.address.clone(), entry.template.clone(), entry.created_at, &game);
+  games().replace(storage, entry.address.clone(), Some(&synced), Some(entry))?;
+  Ok(synced)
+}
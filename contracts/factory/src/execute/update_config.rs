@@ -0,0 +1,34 @@
+use crate::error::ContractError;
+use crate::state::CONFIG;
+use cosmwasm_std::{attr, Addr, DepsMut, Env, MessageInfo, Response};
+
+/// Transfer ownership of the factory or change the code ID of the lottery
+/// contract used for new games.
+pub fn execute_update_config(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  owner: Option<Addr>,
+  lottery_code_id: Option<u64>,
+) -> Result<Response, ContractError> {
+  let mut config = CONFIG.load(deps.storage)?;
+
+  if info.sender != config.owner {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  if let Some(owner) = owner {
+    config.owner = deps.api.addr_validate(owner.as_str())?;
+  }
+  if let Some(lottery_code_id) = lottery_code_id {
+    config.lottery_code_id = lottery_code_id;
+  }
+
+  CONFIG.save(deps.storage, &config)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "update_config"),
+    attr("owner", config.owner),
+    attr("lottery_code_id", config.lottery_code_id.to_string()),
+  ]))
+}
@@ -0,0 +1,162 @@
+use crate::state::{AssetStats, GameEntry};
+use cosmwasm_std::{Addr, Uint128};
+use cw_lottery::asset::{Asset, AssetAmount};
+use cw_lottery::msg::{
+  CallerPolicy, ClaimDeadline, Jackpot, RandomnessSource, RoyaltyMsg, StatsUpdate, WinnerSelection,
+};
+use cw_lottery::state::GameStatus;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+  pub lottery_code_id: u64,
+}
+
+/// Template defines the parts of a game's config that the factory imposes on
+/// every game created from it, along with bounds on what creators may choose.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Template {
+  pub royalties: Vec<RoyaltyMsg>,
+  pub randomness: Option<RandomnessSource>,
+  pub caller_policy: Option<CallerPolicy>,
+  pub jackpot: Option<Jackpot>,
+  #[serde(default)]
+  pub claim_deadline: Option<ClaimDeadline>,
+  pub auto_start_round: bool,
+  // assets in which tickets may be priced, or any if not given
+  pub assets: Option<Vec<Asset>>,
+  pub min_duration_minutes: Option<u32>,
+  pub max_duration_minutes: Option<u32>,
+}
+
+/// CreateGameMsg defines the parts of a game's config left up to its creator,
+/// who becomes the game's owner.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CreateGameMsg {
+  pub template: String,
+  pub id: String,
+  pub name: Option<String>,
+  pub duration_minutes: Option<u32>,
+  pub denom: String,
+  pub cw20_token_address: Option<Addr>,
+  pub ticket_price: String,
+  #[serde(default)]
+  pub extra_prices: Vec<AssetAmount>,
+  pub selection: WinnerSelection,
+  pub has_distinct_winners: bool,
+  pub max_tickets_per_player: Option<u32>,
+  pub funding_threshold: Option<Uint128>,
+  pub commitment: Option<String>,
+  #[serde(default)]
+  pub auto_distribute: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+  CreateGame(CreateGameMsg),
+  SetTemplate {
+    name: String,
+    template: Template,
+  },
+  RemoveTemplate {
+    name: String,
+  },
+  UpdateConfig {
+    owner: Option<Addr>,
+    lottery_code_id: Option<u64>,
+  },
+  // refresh the indexed status and end time of a game
+  SyncGame {
+    address: Addr,
+  },
+  // sent by games created by the factory as players buy tickets, win and claim,
+  // and as the game ends, is canceled or starts a new round
+  RecordStats {
+    updates: Vec<StatsUpdate>,
+  },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+  GetConfig {},
+  GetTemplate {
+    name: String,
+  },
+  GetGame {
+    address: Addr,
+  },
+  GetGamesByOwner {
+    owner: Addr,
+    status: GameStatus,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+  },
+  GetGamesByDenom {
+    // native denom or CW20 token address
+    denom: String,
+    status: GameStatus,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+  },
+  // ordered by end time, with games that have none last
+  GetGamesByEndTime {
+    status: GameStatus,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+  },
+  GetPlayerStats {
+    addr: Addr,
+  },
+  // players ordered by amount of the asset won, most first
+  GetLeaderboard {
+    asset: Asset,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+  },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetConfigResponse {
+  pub owner: Addr,
+  pub lottery_code_id: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetTemplateResponse {
+  pub name: String,
+  pub template: Template,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetGameResponse {
+  pub game: GameEntry,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetGamesResponse {
+  pub games: Vec<GameEntry>,
+  // address to pass as `start_after` to fetch the next page, if any
+  pub next: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetPlayerStatsResponse {
+  pub addr: Addr,
+  pub ticket_count: u64,
+  pub win_count: u64,
+  // stats in each asset the player has spent or won
+  pub assets: Vec<AssetStats>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetLeaderboardResponse {
+  pub entries: Vec<AssetStats>,
+  // address to pass as `start_after` to fetch the next page, if any
+  pub next: Option<Addr>,
+}
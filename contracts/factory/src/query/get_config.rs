@@ -0,0 +1,11 @@
+use crate::msg::GetConfigResponse;
+use crate::state::CONFIG;
+use cosmwasm_std::{Deps, StdResult};
+
+pub fn get_config(deps: Deps) -> StdResult<GetConfigResponse> {
+  let config = CONFIG.load(deps.storage)?;
+  Ok(GetConfigResponse {
+    owner: config.owner,
+    lottery_code_id: config.lottery_code_id,
+  })
+}
@@ -0,0 +1,11 @@
+use crate::msg::GetGameResponse;
+use crate::state::games;
+use cosmwasm_std::{Addr, Deps, StdResult};
+
+pub fn get_game(
+  deps: Deps,
+  address: Addr,
+) -> StdResult<GetGameResponse> {
+  let game = games().load(deps.storage, address)?;
+  Ok(GetGameResponse { game })
+}
@@ -0,0 +1,25 @@
+use super::page_games;
+use crate::msg::GetGamesResponse;
+use crate::state::{games, status_key};
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cw_lottery::state::GameStatus;
+use cw_storage_plus::Bound;
+
+/// Games with the given status whose tickets are priced in a native denom or
+/// CW20 token, in order of address.
+pub fn get_games_by_denom(
+  deps: Deps,
+  denom: String,
+  status: GameStatus,
+  start_after: Option<Addr>,
+  limit: Option<u32>,
+) -> StdResult<GetGamesResponse> {
+  let games = games();
+  let entries = games
+    .idx
+    .denom
+    .prefix((status_key(&status), denom))
+    .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+    .map(|entry| entry.map(|(_, game)| game));
+  page_games(entries, limit)
+}
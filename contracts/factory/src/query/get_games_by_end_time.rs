@@ -0,0 +1,33 @@
+use super::page_games;
+use crate::msg::GetGamesResponse;
+use crate::state::{ends_after_key, games, status_key};
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cw_lottery::state::GameStatus;
+use cw_storage_plus::Bound;
+
+/// Games with the given status, soonest to end first.
+pub fn get_games_by_end_time(
+  deps: Deps,
+  status: GameStatus,
+  start_after: Option<Addr>,
+  limit: Option<u32>,
+) -> StdResult<GetGamesResponse> {
+  let games = games();
+
+  // resume after the cursor game's position in the end time index
+  let min = match start_after {
+    Some(address) => {
+      let game = games.load(deps.storage, address.clone())?;
+      Some(Bound::exclusive((ends_after_key(&game), address)))
+    },
+    None => None,
+  };
+
+  let entries = games
+    .idx
+    .ends_after
+    .sub_prefix(status_key(&status))
+    .range(deps.storage, min, None, Order::Ascending)
+    .map(|entry| entry.map(|(_, game)| game));
+  page_games(entries, limit)
+}
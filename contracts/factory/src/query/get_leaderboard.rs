@@ -0,0 +1,42 @@
+use crate::constants::{DEFAULT_QUERY_LIMIT, MAX_QUERY_LIMIT};
+use crate::msg::GetLeaderboardResponse;
+use crate::state::asset_stats;
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cw_lottery::asset::Asset;
+use cw_storage_plus::Bound;
+
+/// Players ranked by the amount of an asset they've won, most first.
+pub fn get_leaderboard(
+  deps: Deps,
+  asset: Asset,
+  start_after: Option<Addr>,
+  limit: Option<u32>,
+) -> StdResult<GetLeaderboardResponse> {
+  let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+  let stats = asset_stats();
+
+  // resume after the cursor player's position in the winnings index
+  let max = match start_after {
+    Some(player) => {
+      let key = (player, asset.key());
+      let cursor = stats.load(deps.storage, key.clone())?;
+      Some(Bound::exclusive((cursor.won.u128(), key)))
+    },
+    None => None,
+  };
+
+  let entries = stats
+    .idx
+    .winnings
+    .sub_prefix(asset.key())
+    .range(deps.storage, None, max, Order::Descending)
+    .take(limit)
+    .map(|entry| entry.map(|(_, stats)| stats))
+    .collect::<StdResult<Vec<_>>>()?;
+  let next = match entries.last() {
+    Some(entry) if entries.len() == limit => Some(entry.player.clone()),
+    _ => None,
+  };
+
+  Ok(GetLeaderboardResponse { entries, next })
+}
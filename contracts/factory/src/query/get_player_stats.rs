@@ -0,0 +1,21 @@
+use crate::msg::GetPlayerStatsResponse;
+use crate::state::{asset_stats, PLAYER_STATS};
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+
+pub fn get_player_stats(
+  deps: Deps,
+  addr: Addr,
+) -> StdResult<GetPlayerStatsResponse> {
+  let stats = PLAYER_STATS.may_load(deps.storage, &addr)?.unwrap_or_default();
+  let assets = asset_stats()
+    .prefix(addr.clone())
+    .range(deps.storage, None, None, Order::Ascending)
+    .map(|entry| entry.map(|(_, stats)| stats))
+    .collect::<StdResult<Vec<_>>>()?;
+  Ok(GetPlayerStatsResponse {
+    addr,
+    ticket_count: stats.ticket_count,
+    win_count: stats.win_count,
+    assets,
+  })
+}
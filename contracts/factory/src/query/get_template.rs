@@ -0,0 +1,11 @@
+use crate::msg::GetTemplateResponse;
+use crate::state::TEMPLATES;
+use cosmwasm_std::{Deps, StdResult};
+
+pub fn get_template(
+  deps: Deps,
+  name: String,
+) -> StdResult<GetTemplateResponse> {
+  let template = TEMPLATES.load(deps.storage, &name)?;
+  Ok(GetTemplateResponse { name, template })
+}
@@ -0,0 +1,36 @@
+mod get_config;
+mod get_game;
+mod get_games_by_denom;
+mod get_games_by_end_time;
+mod get_games_by_owner;
+mod get_leaderboard;
+mod get_player_stats;
+mod get_template;
+
+pub use get_config::get_config;
+pub use get_game::get_game;
+pub use get_games_by_denom::get_games_by_denom;
+pub use get_games_by_end_time::get_games_by_end_time;
+pub use get_games_by_owner::get_games_by_owner;
+pub use get_leaderboard::get_leaderboard;
+pub use get_player_stats::get_player_stats;
+pub use get_template::get_template;
+
+use crate::constants::{DEFAULT_QUERY_LIMIT, MAX_QUERY_LIMIT};
+use crate::msg::GetGamesResponse;
+use crate::state::GameEntry;
+use cosmwasm_std::StdResult;
+
+/// Collect a page of games, pointing to the next page if the page is full.
+fn page_games(
+  games: impl Iterator<Item = StdResult<GameEntry>>,
+  limit: Option<u32>,
+) -> StdResult<GetGamesResponse> {
+  let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+  let games = games.take(limit).collect::<StdResult<Vec<_>>>()?;
+  let next = match games.last() {
+    Some(game) if games.len() == limit => Some(game.address.clone()),
+    _ => None,
+  };
+  Ok(GetGamesResponse { games, next })
+}
@@ -0,0 +1,173 @@
+use crate::error::ContractError;
+use crate::msg::{InstantiateMsg, Template};
+use cosmwasm_std::{Addr, DepsMut, MessageInfo, Timestamp, Uint128};
+use cw_lottery::asset::Asset;
+use cw_lottery::state::{Game, GameStatus};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+  pub owner: Addr,
+  pub lottery_code_id: u64,
+}
+
+/// GameEntry indexes a game created by the factory. Its status, round and end
+/// time are refreshed whenever the game reports stats or a change of status,
+/// or on `SyncGame`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GameEntry {
+  pub address: Addr,
+  pub id: String,
+  pub owner: Addr,
+  pub template: String,
+  // native denom or CW20 token address in which the ticket price is given
+  pub denom: String,
+  pub status: GameStatus,
+  pub round: u64,
+  pub ends_after: Option<Timestamp>,
+  pub created_at: Timestamp,
+}
+
+pub struct GameIndexes<'a> {
+  pub owner: MultiIndex<'a, (u8, Addr), GameEntry, Addr>,
+  pub denom: MultiIndex<'a, (u8, String), GameEntry, Addr>,
+  pub ends_after: MultiIndex<'a, (u8, u64), GameEntry, Addr>,
+}
+
+impl<'a> IndexList<GameEntry> for GameIndexes<'a> {
+  fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<GameEntry>> + '_> {
+    let indexes: Vec<&dyn Index<GameEntry>> = vec![&self.owner, &self.denom, &self.ends_after];
+    Box::new(indexes.into_iter())
+  }
+}
+
+/// Games created by the factory, keyed by address and indexed by status along
+/// with their owner, denom or end time.
+pub fn games<'a>() -> IndexedMap<'a, Addr, GameEntry, GameIndexes<'a>> {
+  IndexedMap::new(
+    "games",
+    GameIndexes {
+      owner: MultiIndex::new(
+        |_, game| (status_key(&game.status), game.owner.clone()),
+        "games",
+        "games__owner",
+      ),
+      denom: MultiIndex::new(
+        |_, game| (status_key(&game.status), game.denom.clone()),
+        "games",
+        "games__denom",
+      ),
+      ends_after: MultiIndex::new(
+        |_, game| (status_key(&game.status), ends_after_key(game)),
+        "games",
+        "games__ends_after",
+      ),
+    },
+  )
+}
+
+/// PlayerStats holds a player's lifetime counts across every game created by
+/// the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PlayerStats {
+  pub ticket_count: u64,
+  pub win_count: u64,
+}
+
+/// AssetStats holds a player's lifetime amounts of an asset across every game
+/// created by the factory.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetStats {
+  pub player: Addr,
+  pub asset: Asset,
+  pub spent: Uint128,
+  pub won: Uint128,
+  pub claimed: Uint128,
+}
+
+pub struct AssetStatsIndexes<'a> {
+  pub winnings: MultiIndex<'a, (String, u128), AssetStats, (Addr, String)>,
+}
+
+impl<'a> IndexList<AssetStats> for AssetStatsIndexes<'a> {
+  fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<AssetStats>> + '_> {
+    let indexes: Vec<&dyn Index<AssetStats>> = vec![&self.winnings];
+    Box::new(indexes.into_iter())
+  }
+}
+
+/// Players' stats in each asset, keyed by player and asset key and indexed by
+/// amount won in each asset.
+pub fn asset_stats<'a>() -> IndexedMap<'a, (Addr, String), AssetStats, AssetStatsIndexes<'a>> {
+  IndexedMap::new(
+    "asset_stats",
+    AssetStatsIndexes {
+      winnings: MultiIndex::new(
+        |_, stats| (stats.asset.key(), stats.won.u128()),
+        "asset_stats",
+        "asset_stats__winnings",
+      ),
+    },
+  )
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const PLAYER_STATS: Map<&Addr, PlayerStats> = Map::new("player_stats");
+pub const TEMPLATES: Map<&str, Template> = Map::new("templates");
+// template of the game being instantiated, until the instantiation's reply
+pub const PENDING_TEMPLATE: Item<String> = Item::new("pending_template");
+
+/// Initialize contract state data.
+pub fn initialize(
+  deps: DepsMut,
+  info: &MessageInfo,
+  msg: &InstantiateMsg,
+) -> Result<(), ContractError> {
+  CONFIG.save(
+    deps.storage,
+    &Config {
+      owner: info.sender.clone(),
+      lottery_code_id: msg.lottery_code_id,
+    },
+  )?;
+  Ok(())
+}
+
+/// Key under which a status is indexed.
+pub fn status_key(status: &GameStatus) -> u8 {
+  match status {
+    GameStatus::ACTIVE => 0,
+    GameStatus::CLOSED => 1,
+    GameStatus::ENDED => 2,
+    GameStatus::CANCELED => 3,
+  }
+}
+
+/// Key under which a game's end time is indexed, sorting games without one
+/// last.
+pub fn ends_after_key(game: &GameEntry) -> u64 {
+  game.ends_after.map_or(u64::MAX, |ends_after| ends_after.seconds())
+}
+
+impl GameEntry {
+  pub fn new(
+    address: Addr,
+    template: String,
+    created_at: Timestamp,
+    game: &Game,
+  ) -> Self {
+    GameEntry {
+      address,
+      template,
+      created_at,
+      id: game.id.clone(),
+      owner: game.owner.clone(),
+      denom: game.primary_asset().to_string(),
+      status: game.status.clone(),
+      round: game.round,
+      ends_after: game.ends_after,
+    }
+  }
+}
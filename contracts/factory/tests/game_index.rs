@@ -0,0 +1,189 @@
+use cosmwasm_std::{coins, Addr, Coin, Empty};
+use cw_lottery::msg::{ExecuteMsg as LotteryExecuteMsg, WinnerSelection};
+use cw_lottery::state::GameStatus;
+use cw_lottery_factory::contract::{execute, instantiate, query, reply};
+use cw_lottery_factory::msg::{CreateGameMsg, ExecuteMsg, GetGamesResponse, InstantiateMsg, QueryMsg, Template};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+
+const DENOM: &str = "ujuno";
+
+fn lottery() -> Box<dyn Contract<Empty>> {
+  Box::new(
+    ContractWrapper::new(
+      cw_lottery::contract::execute,
+      cw_lottery::contract::instantiate,
+      cw_lottery::contract::query,
+    )
+    .with_reply(cw_lottery::contract::reply),
+  )
+}
+
+fn factory() -> Box<dyn Contract<Empty>> {
+  Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+}
+
+struct Suite {
+  app: App,
+  factory: Addr,
+  game: Addr,
+  owner: Addr,
+  players: Vec<Addr>,
+}
+
+impl Suite {
+  /// Create a game from a factory template, owned by `owner`.
+  fn new() -> Self {
+    let admin = Addr::unchecked("admin");
+    let owner = Addr::unchecked("owner");
+    let players = vec![Addr::unchecked("alice"), Addr::unchecked("bob")];
+    let mut app = App::new(|router, _, storage| {
+      for player in players.iter() {
+        router.bank.init_balance(storage, player, coins(1_000, DENOM)).unwrap();
+      }
+    });
+
+    let lottery_code_id = app.store_code(lottery());
+    let factory_code_id = app.store_code(factory());
+    let factory = app
+      .instantiate_contract(
+        factory_code_id,
+        admin.clone(),
+        &InstantiateMsg { lottery_code_id },
+        &[],
+        "factory",
+        None,
+      )
+      .unwrap();
+
+    app
+      .execute_contract(
+        admin,
+        factory.clone(),
+        &ExecuteMsg::SetTemplate {
+          name: "basic".to_owned(),
+          template: Template {
+            royalties: vec![],
+            randomness: None,
+            caller_policy: None,
+            jackpot: None,
+            claim_deadline: None,
+            auto_start_round: false,
+            assets: None,
+            min_duration_minutes: None,
+            max_duration_minutes: None,
+          },
+        },
+        &[],
+      )
+      .unwrap();
+
+    app
+      .execute_contract(
+        owner.clone(),
+        factory.clone(),
+        &ExecuteMsg::CreateGame(CreateGameMsg {
+          template: "basic".to_owned(),
+          id: "game".to_owned(),
+          name: None,
+          duration_minutes: None,
+          denom: DENOM.to_owned(),
+          cw20_token_address: None,
+          ticket_price: "100".to_owned(),
+          extra_prices: vec![],
+          selection: WinnerSelection::Fixed {
+            pct_split: vec![100],
+            winner_count: 1,
+            max_winner_count: None,
+          },
+          has_distinct_winners: true,
+          max_tickets_per_player: None,
+          funding_threshold: None,
+          commitment: None,
+          auto_distribute: false,
+        }),
+        &[],
+      )
+      .unwrap();
+
+    let mut suite = Suite {
+      app,
+      factory,
+      game: Addr::unchecked(""),
+      owner,
+      players,
+    };
+    let games = suite.games_by_owner(GameStatus::ACTIVE);
+    assert_eq!(games.len(), 1);
+    suite.game = games[0].clone();
+    suite
+  }
+
+  fn execute_game(
+    &mut self,
+    sender: &Addr,
+    msg: &LotteryExecuteMsg,
+    funds: &[Coin],
+  ) {
+    self
+      .app
+      .execute_contract(sender.clone(), self.game.clone(), msg, funds)
+      .unwrap();
+    self.app.update_block(|block| {
+      block.height += 1;
+      block.time = block.time.plus_seconds(5);
+    });
+  }
+
+  fn games_by_owner(
+    &self,
+    status: GameStatus,
+  ) -> Vec<Addr> {
+    let response: GetGamesResponse = self
+      .app
+      .wrap()
+      .query_wasm_smart(
+        &self.factory,
+        &QueryMsg::GetGamesByOwner {
+          owner: self.owner.clone(),
+          status,
+          start_after: None,
+          limit: None,
+        },
+      )
+      .unwrap();
+    response.games.into_iter().map(|game| game.address).collect()
+  }
+}
+
+#[test]
+fn reindexes_ended_game() {
+  let mut suite = Suite::new();
+  for player in suite.players.clone().iter() {
+    let buy = LotteryExecuteMsg::BuyTickets {
+      ticket_count: 1,
+      lucky_phrase: None,
+      numbers: None,
+    };
+    suite.execute_game(player, &buy, &coins(100, DENOM));
+  }
+  let owner = suite.owner.clone();
+  suite.execute_game(&owner, &LotteryExecuteMsg::EndGame { lucky_phrase: None }, &[]);
+
+  assert!(suite.games_by_owner(GameStatus::ACTIVE).is_empty());
+  assert_eq!(suite.games_by_owner(GameStatus::ENDED), vec![suite.game.clone()]);
+}
+
+#[test]
+fn reindexes_canceled_and_restarted_game() {
+  let mut suite = Suite::new();
+  let owner = suite.owner.clone();
+
+  // nobody is credited with anything, but the game still reports the change
+  suite.execute_game(&owner, &LotteryExecuteMsg::CancelGame {}, &[]);
+  assert!(suite.games_by_owner(GameStatus::ACTIVE).is_empty());
+  assert_eq!(suite.games_by_owner(GameStatus::CANCELED), vec![suite.game.clone()]);
+
+  suite.execute_game(&owner, &LotteryExecuteMsg::StartRound {}, &[]);
+  assert!(suite.games_by_owner(GameStatus::CANCELED).is_empty());
+  assert_eq!(suite.games_by_owner(GameStatus::ACTIVE), vec![suite.game.clone()]);
+}
@@ -0,0 +1,437 @@
+use cosmwasm_std::{
+  coins, to_binary, Addr, Binary, Coin, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_lottery::asset::Asset;
+use cw_lottery::msg::{
+  ExecuteMsg as LotteryExecuteMsg, GetWinnersResponse, InstantiateMsg as LotteryInstantiateMsg,
+  QueryMsg as LotteryQueryMsg, ReceiveMsg, WinnerSelection,
+};
+use cw_lottery::state::GameStatus;
+use cw_lottery_factory::contract::{execute, instantiate, query, reply};
+use cw_lottery_factory::msg::{
+  CreateGameMsg, ExecuteMsg, GetGamesResponse, GetLeaderboardResponse, GetPlayerStatsResponse, InstantiateMsg,
+  QueryMsg, Template,
+};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+
+const DENOM: &str = "ujuno";
+
+fn lottery() -> Box<dyn Contract<Empty>> {
+  Box::new(
+    ContractWrapper::new(
+      cw_lottery::contract::execute,
+      cw_lottery::contract::instantiate,
+      cw_lottery::contract::query,
+    )
+    .with_reply(cw_lottery::contract::reply),
+  )
+}
+
+fn factory() -> Box<dyn Contract<Empty>> {
+  Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+}
+
+/// CW20 token that forwards whatever is sent to a contract but fails every
+/// transfer, standing in for a token that blocks the game's payouts.
+fn failing_token() -> Box<dyn Contract<Empty>> {
+  fn execute(
+    _deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+  ) -> StdResult<Response> {
+    match msg {
+      Cw20ExecuteMsg::Send { contract, amount, msg } => {
+        let receive_msg = Cw20ReceiveMsg {
+          sender: info.sender.into(),
+          amount,
+          msg,
+        };
+        Ok(Response::new().add_message(receive_msg.into_cosmos_msg(contract)?))
+      },
+      _ => Err(StdError::generic_err("transfers are disabled")),
+    }
+  }
+  fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+  ) -> StdResult<Response> {
+    Ok(Response::new())
+  }
+  fn query(
+    _deps: Deps,
+    _env: Env,
+    _msg: Empty,
+  ) -> StdResult<Binary> {
+    Ok(Binary::default())
+  }
+  Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+struct Suite {
+  app: App,
+  factory: Addr,
+  lottery_code_id: u64,
+  owner: Addr,
+  players: Vec<Addr>,
+}
+
+impl Suite {
+  fn new() -> Self {
+    let admin = Addr::unchecked("admin");
+    let players = vec![Addr::unchecked("alice"), Addr::unchecked("bob")];
+    let mut app = App::new(|router, _, storage| {
+      for player in players.iter() {
+        router.bank.init_balance(storage, player, coins(1_000, DENOM)).unwrap();
+      }
+    });
+
+    let lottery_code_id = app.store_code(lottery());
+    let factory_code_id = app.store_code(factory());
+    let factory = app
+      .instantiate_contract(
+        factory_code_id,
+        admin.clone(),
+        &InstantiateMsg { lottery_code_id },
+        &[],
+        "factory",
+        None,
+      )
+      .unwrap();
+
+    app
+      .execute_contract(
+        admin,
+        factory.clone(),
+        &ExecuteMsg::SetTemplate {
+          name: "basic".to_owned(),
+          template: Template {
+            royalties: vec![],
+            randomness: None,
+            caller_policy: None,
+            jackpot: None,
+            claim_deadline: None,
+            auto_start_round: false,
+            assets: None,
+            min_duration_minutes: None,
+            max_duration_minutes: None,
+          },
+        },
+        &[],
+      )
+      .unwrap();
+
+    Suite {
+      app,
+      factory,
+      lottery_code_id,
+      owner: Addr::unchecked("owner"),
+      players,
+    }
+  }
+
+  /// Create a game from the factory's template, owned by `owner`.
+  fn game(
+    &mut self,
+    cw20_token_address: Option<Addr>,
+    auto_distribute: bool,
+  ) -> Addr {
+    self
+      .app
+      .execute_contract(
+        self.owner.clone(),
+        self.factory.clone(),
+        &ExecuteMsg::CreateGame(CreateGameMsg {
+          template: "basic".to_owned(),
+          id: "game".to_owned(),
+          name: None,
+          duration_minutes: None,
+          denom: DENOM.to_owned(),
+          cw20_token_address,
+          ticket_price: "100".to_owned(),
+          extra_prices: vec![],
+          selection: WinnerSelection::Fixed {
+            pct_split: vec![100],
+            winner_count: 1,
+            max_winner_count: None,
+          },
+          has_distinct_winners: true,
+          max_tickets_per_player: None,
+          funding_threshold: None,
+          commitment: None,
+          auto_distribute,
+        }),
+        &[],
+      )
+      .unwrap();
+    let response: GetGamesResponse = self
+      .app
+      .wrap()
+      .query_wasm_smart(
+        &self.factory,
+        &QueryMsg::GetGamesByOwner {
+          owner: self.owner.clone(),
+          status: GameStatus::ACTIVE,
+          start_after: None,
+          limit: None,
+        },
+      )
+      .unwrap();
+    response.games.last().unwrap().address.clone()
+  }
+
+  /// Instantiate a game that reports to the factory without having been
+  /// created by it, so that the factory rejects its reports.
+  fn unindexed_game(&mut self) -> Addr {
+    self
+      .app
+      .instantiate_contract(
+        self.lottery_code_id,
+        self.owner.clone(),
+        &LotteryInstantiateMsg {
+          id: "game".to_owned(),
+          owner: None,
+          name: None,
+          duration_minutes: None,
+          denom: DENOM.to_owned(),
+          cw20_token_address: None,
+          ticket_price: "100".to_owned(),
+          extra_prices: vec![],
+          selection: WinnerSelection::Fixed {
+            pct_split: vec![100],
+            winner_count: 1,
+            max_winner_count: None,
+          },
+          has_distinct_winners: true,
+          max_tickets_per_player: None,
+          funding_threshold: None,
+          royalties: vec![],
+          randomness: None,
+          commitment: None,
+          caller_policy: None,
+          auto_start_round: false,
+          auto_distribute: false,
+          jackpot: None,
+          claim_deadline: None,
+          registry: Some(self.factory.clone()),
+        },
+        &[],
+        "game",
+        None,
+      )
+      .unwrap()
+  }
+
+  fn execute_game(
+    &mut self,
+    game: &Addr,
+    sender: &Addr,
+    msg: &LotteryExecuteMsg,
+    funds: &[Coin],
+  ) -> AppResponse {
+    let response = self
+      .app
+      .execute_contract(sender.clone(), game.clone(), msg, funds)
+      .unwrap();
+    self.app.update_block(|block| {
+      block.height += 1;
+      block.time = block.time.plus_seconds(5);
+    });
+    response
+  }
+
+  fn buy(
+    &mut self,
+    game: &Addr,
+    player: &Addr,
+    ticket_count: u32,
+  ) -> AppResponse {
+    let buy = LotteryExecuteMsg::BuyTickets {
+      ticket_count,
+      lucky_phrase: None,
+      numbers: None,
+    };
+    self.execute_game(game, player, &buy, &coins(100 * ticket_count as u128, DENOM))
+  }
+
+  fn winner(
+    &self,
+    game: &Addr,
+    round: u64,
+  ) -> Addr {
+    let response: GetWinnersResponse = self
+      .app
+      .wrap()
+      .query_wasm_smart(
+        game,
+        &LotteryQueryMsg::GetWinners {
+          round: Some(round),
+          start_after: None,
+          limit: None,
+        },
+      )
+      .unwrap();
+    response.winners[0].address.clone()
+  }
+
+  fn stats(
+    &self,
+    player: &Addr,
+  ) -> GetPlayerStatsResponse {
+    self
+      .app
+      .wrap()
+      .query_wasm_smart(&self.factory, &QueryMsg::GetPlayerStats { addr: player.clone() })
+      .unwrap()
+  }
+
+  /// Player's ticket count, win count, and amounts spent, won and claimed.
+  fn totals(
+    &self,
+    player: &Addr,
+  ) -> (u64, u64, Uint128, Uint128, Uint128) {
+    let stats = self.stats(player);
+    let asset = &stats.assets[0];
+    (
+      stats.ticket_count,
+      stats.win_count,
+      asset.spent,
+      asset.won,
+      asset.claimed,
+    )
+  }
+
+  fn leaderboard(&self) -> Vec<(Addr, Uint128)> {
+    let response: GetLeaderboardResponse = self
+      .app
+      .wrap()
+      .query_wasm_smart(
+        &self.factory,
+        &QueryMsg::GetLeaderboard {
+          asset: Asset::Native {
+            denom: DENOM.to_owned(),
+          },
+          start_after: None,
+          limit: None,
+        },
+      )
+      .unwrap();
+    response
+      .entries
+      .into_iter()
+      .map(|entry| (entry.player, entry.won))
+      .collect()
+  }
+}
+
+#[test]
+fn rejected_report_does_not_block_game() {
+  let mut suite = Suite::new();
+  let game = suite.unindexed_game();
+  let alice = suite.players[0].clone();
+
+  let response = suite.buy(&game, &alice, 1);
+
+  // the factory's rejection is swallowed and nothing is recorded
+  assert!(response.events.iter().any(|event| event
+    .attributes
+    .iter()
+    .any(|attr| attr.key == "action" && attr.value == "notify_failed")));
+  assert_eq!(suite.stats(&alice).ticket_count, 0);
+  assert_eq!(suite.app.wrap().query_balance(&game, DENOM).unwrap().amount.u128(), 100);
+}
+
+#[test]
+fn records_wins_claims_and_refunds() {
+  let mut suite = Suite::new();
+  let game = suite.game(None, false);
+  let (alice, bob) = (suite.players[0].clone(), suite.players[1].clone());
+  let owner = suite.owner.clone();
+
+  suite.buy(&game, &alice, 2);
+  suite.buy(&game, &bob, 1);
+  suite.execute_game(&game, &owner, &LotteryExecuteMsg::EndGame { lucky_phrase: None }, &[]);
+  let winner = suite.winner(&game, 0);
+  let loser = if winner == alice { bob.clone() } else { alice.clone() };
+  let spent = |player: &Addr| Uint128::new(if *player == alice { 200 } else { 100 });
+  let tickets = |player: &Addr| if *player == alice { 2 } else { 1 };
+
+  assert_eq!(
+    suite.totals(&winner),
+    (tickets(&winner), 1, spent(&winner), Uint128::new(300), Uint128::zero())
+  );
+  assert_eq!(
+    suite.totals(&loser),
+    (tickets(&loser), 0, spent(&loser), Uint128::zero(), Uint128::zero())
+  );
+
+  let claim = LotteryExecuteMsg::ClaimPrize {
+    positions: None,
+    round: None,
+  };
+  suite.execute_game(&game, &winner, &claim, &[]);
+  assert_eq!(suite.totals(&winner).4, Uint128::new(300));
+  assert_eq!(
+    suite.leaderboard(),
+    vec![(winner.clone(), Uint128::new(300)), (loser.clone(), Uint128::zero())]
+  );
+
+  // tickets refunded in a canceled round no longer count
+  suite.execute_game(&game, &owner, &LotteryExecuteMsg::StartRound {}, &[]);
+  suite.buy(&game, &loser, 3);
+  assert_eq!(suite.totals(&loser).0, tickets(&loser) + 3);
+  suite.execute_game(&game, &owner, &LotteryExecuteMsg::CancelGame {}, &[]);
+  suite.execute_game(&game, &loser, &LotteryExecuteMsg::ClaimRefund { round: None }, &[]);
+  assert_eq!(
+    suite.totals(&loser),
+    (tickets(&loser), 0, spent(&loser), Uint128::zero(), Uint128::zero())
+  );
+  assert_eq!(
+    suite.leaderboard(),
+    vec![(winner.clone(), Uint128::new(300)), (loser, Uint128::zero())]
+  );
+}
+
+#[test]
+fn failed_distribution_is_not_counted_as_claimed() {
+  let mut suite = Suite::new();
+  let token_code_id = suite.app.store_code(failing_token());
+  let token = suite
+    .app
+    .instantiate_contract(token_code_id, suite.owner.clone(), &Empty {}, &[], "token", None)
+    .unwrap();
+  let game = suite.game(Some(token.clone()), true);
+  let owner = suite.owner.clone();
+
+  for player in suite.players.clone().iter() {
+    let send = Cw20ExecuteMsg::Send {
+      contract: game.to_string(),
+      amount: Uint128::new(100),
+      msg: to_binary(&ReceiveMsg::BuyTickets {
+        ticket_count: 1,
+        lucky_phrase: None,
+        numbers: None,
+      })
+      .unwrap(),
+    };
+    suite
+      .app
+      .execute_contract(player.clone(), token.clone(), &send, &[])
+      .unwrap();
+  }
+  let response = suite.execute_game(&game, &owner, &LotteryExecuteMsg::EndGame { lucky_phrase: None }, &[]);
+  assert!(response.events.iter().any(|event| event
+    .attributes
+    .iter()
+    .any(|attr| attr.key == "action" && attr.value == "distribution_failed")));
+
+  // the prize was won but, having never arrived, isn't counted as claimed
+  let winner = suite.winner(&game, 0);
+  assert_eq!(
+    suite.totals(&winner),
+    (1, 1, Uint128::new(100), Uint128::new(200), Uint128::zero())
+  );
+}
@@ -4,8 +4,9 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 use cosmwasm_std::Coin;
 
+use cw_lottery::asset::AssetAmount;
 use cw_lottery::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use cw_lottery::state::{Game, GameStatus, Player, TicketOrder, Winner};
+use cw_lottery::state::{Commitment, Game, GameStatus, Player, Royalty, SeedFinalization, TicketOrder, Winner};
 
 fn main() {
   let mut out_dir = current_dir().unwrap();
@@ -21,5 +22,9 @@ fn main() {
   export_schema(&schema_for!(TicketOrder), &out_dir);
   export_schema(&schema_for!(Player), &out_dir);
   export_schema(&schema_for!(Winner), &out_dir);
+  export_schema(&schema_for!(Royalty), &out_dir);
+  export_schema(&schema_for!(Commitment), &out_dir);
+  export_schema(&schema_for!(SeedFinalization), &out_dir);
+  export_schema(&schema_for!(AssetAmount), &out_dir);
   export_schema(&schema_for!(Coin), &out_dir);
 }
@@ -0,0 +1,74 @@
+use cosmwasm_std::{to_binary, Addr, BankMsg, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Asset identifies a native denom or CW20 token in which tickets can be paid
+/// for and prizes are paid out.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Asset {
+  Native { denom: String },
+  Cw20 { address: Addr },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetAmount {
+  pub asset: Asset,
+  pub amount: Uint128,
+}
+
+impl Asset {
+  /// Key under which amounts of this asset are stored.
+  pub fn key(&self) -> String {
+    match self {
+      Asset::Native { denom } => format!("native:{}", denom),
+      Asset::Cw20 { address } => format!("cw20:{}", address),
+    }
+  }
+
+  /// Build the message sending the given amount of this asset from the
+  /// contract to the recipient.
+  pub fn transfer_msg(
+    &self,
+    recipient: &Addr,
+    amount: Uint128,
+  ) -> StdResult<CosmosMsg> {
+    Ok(match self {
+      Asset::Native { denom } => CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.clone().into(),
+        amount: vec![Coin::new(amount.u128(), denom.clone())],
+      }),
+      Asset::Cw20 { address } => CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: address.clone().into(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+          recipient: recipient.clone().into(),
+          amount,
+        })?,
+        funds: vec![],
+      }),
+    })
+  }
+}
+
+impl fmt::Display for Asset {
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter,
+  ) -> fmt::Result {
+    match self {
+      Asset::Native { denom } => write!(f, "{}", denom),
+      Asset::Cw20 { address } => write!(f, "{}", address),
+    }
+  }
+}
+
+impl fmt::Display for AssetAmount {
+  fn fmt(
+    &self,
+    f: &mut fmt::Formatter,
+  ) -> fmt::Result {
+    write!(f, "{}{}", self.amount, self.asset)
+  }
+}
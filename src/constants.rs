@@ -1,5 +1,50 @@
-pub const GELOTTO_ADDR: &str = "juno1jume25ttjlcaqqjzjjqx9humvze3vcc8z87szj";
-pub const GELOTTO_ANNUAL_PRIZE_ADDR: &str = "juno1fxu5as8z5qxdulujzph3rm6c39r8427mjnx99r";
-pub const GELOTTO_NFT_SERIES_1_REWARDS_ADDR: &str = "juno18fd2xax0uh9dxusg8uae5rkeu8a4sv3gk6zm7h";
-pub const GELOTTO_NFT_SERIES_2_REWARDS_ADDR: &str = "juno13c97054tjktvzvgqe2xfxj28j6wmhhlz03ut32";
-pub const GELOTTO_OWNER_REWARDS_ADDR: &str = "juno1dunhw3y4m6lu642lk20hfq9q3scr70l2vuyrwj";
+/// Time after a game's `ends_after` during which only the owner may cancel it.
+/// Once elapsed, anyone may cancel an active game so that players can reclaim
+/// their funds.
+pub const CANCEL_GRACE_PERIOD_SECONDS: u64 = 60 * 60 * 24;
+
+/// Royalties are expressed in basis points, i.e. 1/10000ths of the jackpot.
+pub const BASIS_POINTS_DENOMINATOR: u128 = 10_000;
+
+/// Upper bound on the sum of all royalties taken out of a game's jackpot.
+pub const MAX_TOTAL_ROYALTY_BASIS_POINTS: u16 = 2_000;
+
+/// Royalties of games created before royalties were configurable, in basis
+/// points. Such games also paid their owner `LEGACY_OWNER_ROYALTY_BASIS_POINTS`.
+pub const LEGACY_ROYALTIES: [(&str, u16, &str); 5] = [
+  ("juno1jume25ttjlcaqqjzjjqx9humvze3vcc8z87szj", 250, "gelotto"),
+  ("juno1fxu5as8z5qxdulujzph3rm6c39r8427mjnx99r", 350, "annual_prize"),
+  ("juno18fd2xax0uh9dxusg8uae5rkeu8a4sv3gk6zm7h", 100, "nft_series_1"),
+  ("juno13c97054tjktvzvgqe2xfxj28j6wmhhlz03ut32", 100, "nft_series_2"),
+  ("juno1dunhw3y4m6lu642lk20hfq9q3scr70l2vuyrwj", 100, "owner_rewards"),
+];
+
+/// Royalty paid to the owner of games created before royalties were
+/// configurable, in basis points.
+pub const LEGACY_OWNER_ROYALTY_BASIS_POINTS: u16 = 100;
+
+/// Number of items returned by paginated queries when no limit is given.
+pub const DEFAULT_QUERY_LIMIT: u32 = 50;
+
+/// Upper bound on the number of items returned by paginated queries.
+pub const MAX_QUERY_LIMIT: u32 = 200;
+
+/// Number of winners paid per transaction in games that distribute prizes
+/// automatically, with the rest left to `DistributeRemaining`.
+pub const MAX_DISTRIBUTIONS_PER_TX: usize = 25;
+
+/// Upper bound on the number of draw states explored to compute exact odds in
+/// games with distinct winners, beyond which the odds are approximated.
+pub const MAX_ODDS_STATES: usize = 5_000;
+
+/// Upper bound on the number of players whose ticket counts are read to compute
+/// exact odds in games with distinct winners, beyond which the odds are
+/// approximated.
+pub const MAX_ODDS_PLAYERS: usize = 1_000;
+
+/// Upper bound on the number of tickets sold per round of a lotto game, since
+/// ending the round matches every ticket against the winning numbers.
+pub const MAX_LOTTO_TICKETS_PER_ROUND: u32 = 10_000;
+
+/// Mixed into a game's seed to derive the PRNG used for its jackpot draw.
+pub const JACKPOT_SEED_LABEL: &[u8] = b"jackpot";
@@ -1,10 +1,11 @@
-#[cfg(not(feature = "library"))]
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::query;
-use crate::{execute, state};
+use crate::state::GAME;
+use crate::{execute, policy, registry, state};
+#[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult};
 use cw2::set_contract_version;
 
 const CONTRACT_NAME: &str = "crates.io:cw-lottery";
@@ -22,7 +23,7 @@ pub fn instantiate(
   Ok(
     Response::new()
       .add_attribute("action", "instantiate")
-      .add_attribute("owner", info.sender)
+      .add_attribute("owner", msg.owner.clone().unwrap_or(info.sender))
       .add_attribute("id", msg.id),
   )
 }
@@ -34,41 +35,92 @@ pub fn execute(
   info: MessageInfo,
   msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
-  // XXX: hack to disallow contracts from executing lottery functions.
-  // ...based on the assumption that contract addresses are longer than
-  // normal wallet addresses.
-  if info.sender.as_str().len() != 43 {
-    return Err(ContractError::NotAuthorized {});
+  if msg.is_restricted() {
+    let game = GAME.load(deps.storage)?;
+    policy::authorize_caller(&deps.querier, &game.caller_policy, &info.sender)?;
   }
   match msg {
     ExecuteMsg::EndGame { lucky_phrase } => execute::end_game(deps, env, info, &lucky_phrase),
-    ExecuteMsg::ClaimPrize { positions } => execute::claim_prize(deps, env, info, &positions),
+    ExecuteMsg::ClaimPrize { positions, round } => execute::claim_prize(deps, env, info, positions, round),
     ExecuteMsg::BuyTickets {
       ticket_count,
       lucky_phrase,
-    } => execute::buy_tickets(deps, env, info, ticket_count, &lucky_phrase),
+      numbers,
+    } => execute::buy_tickets(deps, env, info, ticket_count, &lucky_phrase, &numbers),
+    ExecuteMsg::DistributeRemaining { round } => execute::distribute_remaining(deps, env, info, round),
+    ExecuteMsg::SweepUnclaimed { round } => execute::sweep_unclaimed(deps, env, info, round),
+    ExecuteMsg::CancelGame {} => execute::cancel_game(deps, env, info),
+    ExecuteMsg::ClaimRefund { round } => execute::claim_refund(deps, env, info, round),
+    ExecuteMsg::StartRound {} => execute::start_round(deps, env, info),
+    ExecuteMsg::SeedJackpot {} => execute::seed_jackpot(deps, env, info),
+    ExecuteMsg::Commit { hash } => execute::commit(deps, env, info, &hash),
+    ExecuteMsg::Reveal { secret } => execute::reveal(deps, env, info, &secret),
+    ExecuteMsg::SubmitBeacon { signature } => execute::submit_beacon(deps, env, info, &signature),
+    ExecuteMsg::ReceiveRandomness { callback } => execute::receive_randomness(deps, env, info, &callback),
+    ExecuteMsg::Receive(receive_msg) => execute::receive(deps, env, info, receive_msg),
+  }
+}
+
+/// Every submessage that replies is either a notification sent to the game's
+/// stats registry or a prize transfer sent by `end_game` or
+/// `distribute_remaining`.
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+  deps: DepsMut,
+  env: Env,
+  reply: Reply,
+) -> Result<Response, ContractError> {
+  match reply.id {
+    registry::REPLY_ID => Ok(registry::reply(reply)),
+    _ => execute::distribute_reply(deps, env, reply),
   }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(
   deps: Deps,
-  _env: Env,
+  env: Env,
   msg: QueryMsg,
 ) -> StdResult<Binary> {
   let result = match msg {
-    QueryMsg::GetWinners {} => to_binary(&query::get_winners(deps)?),
-    QueryMsg::GetPlayers {} => to_binary(&query::get_players(deps)?),
-    QueryMsg::GetPlayerTicketCount { addr } => to_binary(&query::get_player_ticket_count(deps, addr)?),
+    QueryMsg::GetGame { round } => to_binary(&query::get_game(deps, env, round)?),
+    QueryMsg::GetWinners {
+      round,
+      start_after,
+      limit,
+    } => to_binary(&query::get_winners(deps, round, start_after, limit)?),
+    QueryMsg::GetPlayers {
+      round,
+      order_by,
+      start_after,
+      limit,
+    } => to_binary(&query::get_players(deps, round, order_by, start_after, limit)?),
+    QueryMsg::GetPlayerTicketCount { addr, round } => to_binary(&query::get_player_ticket_count(deps, addr, round)?),
+    QueryMsg::GetRoyalties {} => to_binary(&query::get_royalties(deps)?),
+    QueryMsg::VerifyDraw { round } => to_binary(&query::verify_draw(deps, round)?),
+    QueryMsg::GetJackpot { round } => to_binary(&query::get_jackpot(deps, round)?),
+    QueryMsg::GetClaimable { addr } => to_binary(&query::get_claimable(deps, addr)?),
+    QueryMsg::GetOdds {
+      addr,
+      additional_tickets,
+    } => to_binary(&query::get_odds(deps, addr, additional_tickets)?),
+    QueryMsg::SimulateEndGame {} => to_binary(&query::simulate_end_game(deps)?),
+    QueryMsg::GetSeedHistory {
+      round,
+      start_after,
+      limit,
+    } => to_binary(&query::get_seed_history(deps, round, start_after, limit)?),
   }?;
   Ok(result)
 }
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(
-  _deps: DepsMut,
-  _env: Env,
+  deps: DepsMut,
+  env: Env,
   _msg: MigrateMsg,
 ) -> Result<Response, ContractError> {
-  Ok(Response::default())
+  set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+  state::migrate(deps, &env)?;
+  Ok(Response::new().add_attribute("action", "migrate"))
 }
@@ -0,0 +1,152 @@
+use crate::constants::{BASIS_POINTS_DENOMINATOR, JACKPOT_SEED_LABEL};
+use crate::error::ContractError;
+use crate::msg::{PrizeTier, WinnerSelection};
+use crate::random;
+use crate::random::pcg64_from_game_seed;
+use crate::state::{find_ticket_order, Game, ORDERS};
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// A single draw from the game's PRNG, as performed when selecting winners.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DrawStep {
+  // raw PCG64 output from which the ticket index was derived
+  pub random_value: u64,
+  pub ticket_index: u64,
+  pub address: Addr,
+  // true if the ticket's owner had already been drawn and the game requires
+  // distinct winners, in which case the step doesn't produce a winner
+  pub is_duplicate: bool,
+}
+
+/// The draw deciding whether a game's grand prize hits its jackpot.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JackpotDraw {
+  // raw PCG64 output from which the draw in basis points was derived
+  pub random_value: u64,
+  pub basis_points: u64,
+  pub is_hit: bool,
+}
+
+/// Return the number of winners to draw and the percent of the winnings to
+/// which each position is entitled, if fixed.
+pub fn winner_count(game: &Game) -> (u32, Vec<u8>) {
+  match game.selection.clone() {
+    WinnerSelection::Fixed {
+      winner_count,
+      max_winner_count,
+      pct_split,
+    } => {
+      let mut n_winners = std::cmp::min(game.player_count, winner_count);
+      if let Some(n_max) = max_winner_count {
+        if n_max > 0 {
+          n_winners = std::cmp::min(n_max, n_winners);
+        }
+      }
+      (n_winners, pct_split)
+    },
+    WinnerSelection::Percent { pct_player_count } => {
+      let n_winners = std::cmp::max(1, game.player_count * (pct_player_count as u32) / 100);
+      (n_winners, vec![])
+    },
+    // lotto tickets win by matching drawn numbers rather than being drawn
+    WinnerSelection::Lotto { .. } => (0, vec![]),
+  }
+}
+
+/// Draw tickets using the game's seed until `n_winners` winners are found,
+/// returning every step along the way. This is deterministic given the seed
+/// and ticket orders, so that anyone can replay it.
+pub fn draw(
+  storage: &dyn Storage,
+  game: &Game,
+  n_winners: u32,
+) -> Result<Vec<DrawStep>, ContractError> {
+  let mut steps: Vec<DrawStep> = Vec::with_capacity(n_winners as usize);
+  let mut n_found = 0u32;
+  let mut rng = pcg64_from_game_seed(&game.seed)?;
+  let mut visited: HashSet<Addr> = HashSet::with_capacity(n_winners as usize);
+
+  while n_found < n_winners {
+    let (random_value, i) = rng.next_bounded_with_raw(game.ticket_count as u64);
+    let addr = find_ticket_order(storage, game.round, game.order_count, i)?.owner;
+    let is_duplicate = game.has_distinct_winners && visited.contains(&addr);
+    if !is_duplicate {
+      visited.insert(addr.clone());
+      n_found += 1;
+    }
+    steps.push(DrawStep {
+      random_value,
+      ticket_index: i,
+      address: addr,
+      is_duplicate,
+    });
+  }
+
+  Ok(steps)
+}
+
+/// Draw whether the grand prize hits the jackpot, for games that have one. The
+/// PRNG is seeded from the game's seed mixed with a fixed label, so that this
+/// draw is independent of the draw of winning tickets.
+pub fn draw_jackpot(game: &Game) -> Result<Option<JackpotDraw>, ContractError> {
+  // in lotto games, the jackpot is hit by matching every number instead
+  let jackpot = match (&game.jackpot, &game.selection) {
+    (_, WinnerSelection::Lotto { .. }) | (None, _) => return Ok(None),
+    (Some(jackpot), _) => jackpot,
+  };
+  let seed = random::seed::mix(&game.seed, &[JACKPOT_SEED_LABEL.to_vec()]);
+  let mut rng = pcg64_from_game_seed(&seed)?;
+  let (random_value, basis_points) = rng.next_bounded_with_raw(BASIS_POINTS_DENOMINATOR as u64);
+  Ok(Some(JackpotDraw {
+    random_value,
+    basis_points,
+    is_hit: basis_points < jackpot.hit_basis_points as u64,
+  }))
+}
+
+/// Count each player's tickets in each prize tier of a lotto game, given the
+/// winning numbers. Tickets only land in the first tier with their match count.
+pub fn count_lotto_tiers(
+  storage: &dyn Storage,
+  game: &Game,
+  prize_tiers: &[PrizeTier],
+  winning_numbers: &[u8],
+) -> StdResult<Vec<BTreeMap<Addr, u32>>> {
+  let mut tiers: Vec<BTreeMap<Addr, u32>> = vec![BTreeMap::new(); prize_tiers.len()];
+  for result in ORDERS.prefix(game.round).range(storage, None, None, Order::Ascending) {
+    let (_, order) = result?;
+    for ticket in order.numbers.iter() {
+      let matches = ticket.iter().filter(|number| winning_numbers.contains(number)).count() as u8;
+      if let Some(i) = prize_tiers.iter().position(|tier| tier.matches == matches) {
+        *tiers[i].entry(order.owner.clone()).or_default() += 1;
+      }
+    }
+  }
+  Ok(tiers)
+}
+
+/// Draw the winning numbers of a lotto game using the game's seed, returning
+/// them in ascending order.
+pub fn draw_numbers(game: &Game) -> Result<Option<Vec<u8>>, ContractError> {
+  let (numbers_per_ticket, number_range) = match game.selection {
+    WinnerSelection::Lotto {
+      numbers_per_ticket,
+      number_range,
+      ..
+    } => (numbers_per_ticket, number_range),
+    _ => return Ok(None),
+  };
+  let mut rng = pcg64_from_game_seed(&game.seed)?;
+  let mut numbers: Vec<u8> = Vec::with_capacity(numbers_per_ticket as usize);
+  while numbers.len() < numbers_per_ticket as usize {
+    let number = 1 + rng.next_bounded(number_range as u64) as u8;
+    if !numbers.contains(&number) {
+      numbers.push(number);
+    }
+  }
+  numbers.sort_unstable();
+  Ok(Some(numbers))
+}
@@ -1,4 +1,4 @@
-use cosmwasm_std::{StdError, Uint128};
+use cosmwasm_std::{Addr, StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -30,6 +30,9 @@ pub enum ContractError {
   #[error("ExceededMaxTicketsPerPlayer")]
   ExceededMaxTicketsPerPlayer {},
 
+  #[error("ExceededMaxLottoTickets")]
+  ExceededMaxLottoTickets { max_ticket_count: u32 },
+
   #[error("UnderFundingThreshold")]
   UnderFundingThreshold { funding_threshold: Uint128 },
 
@@ -38,4 +41,118 @@ pub enum ContractError {
 
   #[error("ExcessFunds")]
   ExcessFunds {},
+
+  #[error("NotCanceled")]
+  NotCanceled {},
+
+  #[error("AlreadyRefunded")]
+  AlreadyRefunded {},
+
+  #[error("ExcessRoyalties")]
+  ExcessRoyalties { max_basis_points: u16 },
+
+  #[error("NotCommitter")]
+  NotCommitter {},
+
+  #[error("NotClosed")]
+  NotClosed {},
+
+  #[error("MissingCommitment")]
+  MissingCommitment { committer: Addr },
+
+  #[error("AwaitingReveal")]
+  AwaitingReveal { committer: Addr },
+
+  #[error("InvalidReveal")]
+  InvalidReveal {},
+
+  #[error("CommitterLuckyPhrase")]
+  CommitterLuckyPhrase {},
+
+  #[error("InvalidDrandConfig")]
+  InvalidDrandConfig {},
+
+  #[error("InvalidBeacon")]
+  InvalidBeacon { round: u64 },
+
+  #[error("AwaitingBeacon")]
+  AwaitingBeacon { round: u64 },
+
+  #[error("AwaitingRandomness")]
+  AwaitingRandomness {},
+
+  #[error("InvalidJobId")]
+  InvalidJobId { job_id: String },
+
+  #[error("CallerNotAllowed")]
+  CallerNotAllowed { caller: Addr },
+
+  #[error("UnsupportedAsset")]
+  UnsupportedAsset {},
+
+  #[error("DuplicateAsset")]
+  DuplicateAsset { asset: String },
+
+  #[error("RoundInProgress")]
+  RoundInProgress {},
+
+  #[error("InvalidJackpot")]
+  InvalidJackpot {},
+
+  #[error("NoJackpot")]
+  NoJackpot {},
+
+  #[error("InvalidNumbers")]
+  InvalidNumbers {},
+
+  #[error("InvalidWinnerCount")]
+  InvalidWinnerCount {},
+
+  #[error("InvalidMaxWinnerCount")]
+  InvalidMaxWinnerCount { max_winner_count: u32, winner_count: u32 },
+
+  #[error("PctSplitLengthMismatch")]
+  PctSplitLengthMismatch { winner_count: u32, split_count: u32 },
+
+  #[error("PctSplitTotalNot100")]
+  PctSplitTotalNot100 { total: u32 },
+
+  #[error("ZeroPctSplit")]
+  ZeroPctSplit { position: u32 },
+
+  #[error("InvalidPctPlayerCount")]
+  InvalidPctPlayerCount { pct_player_count: u8 },
+
+  #[error("InvalidNumbersPerTicket")]
+  InvalidNumbersPerTicket { numbers_per_ticket: u8, number_range: u8 },
+
+  #[error("NoPrizeTiers")]
+  NoPrizeTiers {},
+
+  #[error("PrizeTierTotalExceeds100")]
+  PrizeTierTotalExceeds100 { total: u32 },
+
+  #[error("InvalidPrizeTierMatches")]
+  InvalidPrizeTierMatches { matches: u8 },
+
+  #[error("DuplicatePrizeTier")]
+  DuplicatePrizeTier { matches: u8 },
+
+  #[error("NothingToDistribute")]
+  NothingToDistribute {},
+
+  #[error("InvalidClaimDeadline")]
+  InvalidClaimDeadline {},
+
+  #[error("NoClaimDeadline")]
+  NoClaimDeadline {},
+
+  #[error("ClaimDeadlineNotReached")]
+  ClaimDeadlineNotReached {},
+
+  #[error("NothingToSweep")]
+  NothingToSweep {},
+
+  #[error("NothingToClaim")]
+  NothingToClaim {},
 }
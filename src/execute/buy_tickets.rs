@@ -1,10 +1,12 @@
+use crate::asset::{Asset, AssetAmount};
+use crate::constants::MAX_LOTTO_TICKETS_PER_ROUND;
 use crate::error::ContractError;
+use crate::msg::WinnerSelection;
 use crate::random;
-use crate::state::{
-  Game, Player, TicketOrder, ADDR_2_INDEX, GAME, INDEX_2_ADDR, INDICES, ORDERS, PLAYERS, PREV_HEIGHT,
-};
+use crate::registry;
+use crate::state::{players, Game, GameStatus, Player, TicketOrder, GAME, ORDERS, PAYMENTS, POTS, PREV_HEIGHT};
 use cosmwasm_std::{
-  attr, to_binary, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, SubMsg, Uint128, WasmMsg,
+  attr, to_binary, Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
 };
 use cw20::Cw20ExecuteMsg;
 
@@ -17,17 +19,113 @@ pub fn execute_buy_tickets(
   info: MessageInfo,
   ticket_count: u32,
   lucky_phrase: &Option<String>,
+  numbers: &Option<Vec<Vec<u8>>>,
 ) -> Result<Response, ContractError> {
-  let mut game: Game = GAME.load(deps.storage)?;
-  let owner = info.sender.clone();
+  let game = record_ticket_order(deps.storage, &env, &info.sender, ticket_count, lucky_phrase, numbers)?;
+
+  // transfer payment from player to the contract, in the native asset sent
+  // or, if none was sent, in the game's CW20 token.
+  let response = match (info.funds.as_slice(), &game.cw20_token_address) {
+    ([], Some(cw20_token_address)) => {
+      let payment_amount = game.ticket_price * Uint128::from(ticket_count);
+      let asset = game.primary_asset();
+      record_payment(deps.storage, game.round, &info.sender, &asset, payment_amount)?;
+
+      // perform CW20 transfer from sender to contract.  note that the cw20
+      // token allowance for this contract must be set.
+      let transfer_from = Cw20ExecuteMsg::TransferFrom {
+        owner: info.sender.clone().into(),
+        recipient: env.contract.address.clone().into(),
+        amount: payment_amount,
+      };
 
-  // amount owed by player in exchange for the tickets:
-  let payment_amount = game.ticket_price * Uint128::from(ticket_count);
+      let execute_msg = WasmMsg::Execute {
+        contract_addr: cw20_token_address.clone().into(),
+        msg: to_binary(&transfer_from)?,
+        funds: vec![],
+      };
 
-  if PLAYERS.has(deps.storage, owner.clone()) {
+      Response::new()
+        .add_message(execute_msg)
+        .add_submessages(notify_purchase(
+          &game,
+          &info.sender,
+          ticket_count,
+          &asset,
+          payment_amount,
+        )?)
+        .add_attributes(vec![
+          attr("action", "buy_tickets"),
+          attr("ticket_count", ticket_count.to_string()),
+          attr("asset", asset.to_string()),
+        ])
+    },
+    ([coin], _) => {
+      // If we're here, we're using a native asset type, not a CW20 token.
+      // Verify that the exact funds required for the order exist.
+      let asset = Asset::Native {
+        denom: coin.denom.clone(),
+      };
+      let payment_amount = match game.price_of(&asset) {
+        Some(price) => price * Uint128::from(ticket_count),
+        None => return Err(ContractError::UnsupportedAsset {}),
+      };
+      if coin.amount < payment_amount {
+        return Err(ContractError::InsufficientFunds {});
+      } else if coin.amount > payment_amount {
+        return Err(ContractError::ExcessFunds {});
+      }
+      record_payment(deps.storage, game.round, &info.sender, &asset, payment_amount)?;
+
+      Response::new()
+        .add_submessages(notify_purchase(
+          &game,
+          &info.sender,
+          ticket_count,
+          &asset,
+          payment_amount,
+        )?)
+        .add_attributes(vec![
+          attr("action", "buy_tickets"),
+          attr("ticket_count", ticket_count.to_string()),
+          attr("asset", asset.to_string()),
+        ])
+    },
+    // 0 funds
+    ([], None) => return Err(ContractError::InsufficientFunds {}),
+    _ => return Err(ContractError::ExcessFunds {}),
+  };
+
+  Ok(response)
+}
+
+/// Add a ticket order for the buyer, updating the game's player and ticket
+/// counts and its seed. Payment is left up to the caller.
+pub(crate) fn record_ticket_order(
+  storage: &mut dyn Storage,
+  env: &Env,
+  buyer: &Addr,
+  ticket_count: u32,
+  lucky_phrase: &Option<String>,
+  numbers: &Option<Vec<Vec<u8>>>,
+) -> Result<Game, ContractError> {
+  let mut game: Game = GAME.load(storage)?;
+
+  // abort if the game has been ended or canceled
+  if game.status != GameStatus::ACTIVE {
+    return Err(ContractError::NotActive {});
+  }
+
+  let numbers = validate_numbers(&game, ticket_count, numbers)?;
+
+  if players().has(storage, (game.round, buyer.clone())) {
     // update player's ticket count
-    PLAYERS.update(deps.storage, owner.clone(), |p| -> Result<_, ContractError> {
-      let mut player = p.unwrap_or_else(|| Player { ticket_count: 0 });
+    players().update(storage, (game.round, buyer.clone()), |p| -> Result<_, ContractError> {
+      let mut player = p.unwrap_or(Player {
+        ticket_count: 0,
+        has_refunded: false,
+        joined_at: game.player_count,
+      });
       if let Some(max_tickets_per_player) = game.max_tickets_per_player {
         // don't let player buy more tickets than max allowed, unless N/A
         if player.ticket_count + ticket_count > max_tickets_per_player {
@@ -45,96 +143,120 @@ pub fn execute_buy_tickets(
         return Err(ContractError::ExceededMaxTicketsPerPlayer {});
       }
     }
+    players().save(
+      storage,
+      (game.round, buyer.clone()),
+      &Player {
+        ticket_count,
+        has_refunded: false,
+        joined_at: game.player_count,
+      },
+    )?;
     game.player_count += 1;
-
-    PLAYERS.save(deps.storage, owner.clone(), &Player { ticket_count })?;
-    ADDR_2_INDEX.save(deps.storage, info.sender.clone(), &game.player_count)?;
-    INDEX_2_ADDR.save(deps.storage, game.player_count, &owner)?;
   }
 
-  // update game's player count and PRNG seed
-  game.seed = random::seed::update(&game, &owner, ticket_count, env.block.height, &lucky_phrase);
-  game.ticket_count += ticket_count;
-
-  GAME.save(deps.storage, &game)?;
-
-  let address_index = ADDR_2_INDEX.load(deps.storage, owner.clone())?;
-
-  INDICES.update(deps.storage, |mut indices: Vec<u32>| -> Result<_, ContractError> {
-    for _ in 0..ticket_count {
-      indices.push(address_index)
-    }
-    Ok(indices)
-  })?;
+  // update game's PRNG seed
+  let lucky_phrase_hash = lucky_phrase.as_ref().map(|phrase| random::seed::digest(phrase));
+  game.seed = random::seed::update(&game, buyer, ticket_count, env.block.height, &lucky_phrase_hash);
 
-  ORDERS.update(
-    deps.storage,
-    |mut orders: Vec<TicketOrder>| -> Result<_, ContractError> {
-      orders.push(TicketOrder {
-        owner: owner.clone(),
-        count: ticket_count,
-        cum_count: (ticket_count as u64)
-          + if orders.len() > 0 {
-            orders[orders.len() - 1].cum_count
-          } else {
-            0
-          },
-      });
-      Ok(orders)
+  // append the order, tracking the cumulative number of tickets sold so far
+  // so that winning tickets can be located by binary search, along with the
+  // resulting seed for auditing.
+  ORDERS.save(
+    storage,
+    (game.round, game.order_count),
+    &TicketOrder {
+      owner: buyer.clone(),
+      count: ticket_count,
+      cum_count: game.ticket_count as u64 + ticket_count as u64,
+      seed: game.seed.clone(),
+      block_height: env.block.height,
+      lucky_phrase_hash,
+      numbers,
     },
   )?;
 
-  PREV_HEIGHT.save(deps.storage, &env.block.height)?;
+  game.ticket_count += ticket_count;
+  game.order_count += 1;
 
-  // transfer payment from player to the contract
-  let response = match game.cw20_token_address {
-    Some(cw20_token_address) => {
-      // perform CW20 transfer from sender to contract.  note that the cw20
-      // token allowance for this contract must be set.
-      let transfer_from = Cw20ExecuteMsg::TransferFrom {
-        owner: info.sender.clone().into(),
-        recipient: env.contract.address.clone().into(),
-        amount: payment_amount,
-      };
+  GAME.save(storage, &game)?;
 
-      let execute_msg = WasmMsg::Execute {
-        contract_addr: cw20_token_address.clone().into(),
-        msg: to_binary(&transfer_from)?,
-        funds: vec![],
-      };
+  PREV_HEIGHT.save(storage, &env.block.height)?;
 
-      Response::new()
-        .add_submessage(SubMsg::new(execute_msg))
-        .add_attributes(vec![
-          attr("action", "buy_tickets"),
-          attr("ticket_count", ticket_count.to_string()),
-        ])
-    },
-    None => {
-      // If we're here, we're using a native asset type, not a CW20 token.
-      // Verify that the exact funds required for the order exist.
-      if let Some(coin) = info.funds.iter().find(|coin| -> bool { coin.denom == game.denom }) {
-        if coin.amount < payment_amount {
-          return Err(ContractError::InsufficientFunds {});
-        } else if coin.amount > payment_amount {
-          return Err(ContractError::ExcessFunds {});
-        }
-      } else {
-        // 0 funds
-        return Err(ContractError::InsufficientFunds {});
-      }
-      // Perform transfer of IBC asset from sender to contract.
-      let message = CosmosMsg::Bank(BankMsg::Send {
-        to_address: env.contract.address.into_string(),
-        amount: vec![Coin::new(payment_amount.u128(), game.denom)],
-      });
+  Ok(game)
+}
 
-      Response::new().add_message(message).add_attributes(vec![
-        attr("action", "buy_tickets"),
-        attr("ticket_count", ticket_count.to_string()),
-      ])
-    },
+/// Ensure that numbers were picked for every ticket in lotto games, and only in
+/// lotto games, returning each ticket's numbers in ascending order. Lotto rounds
+/// are capped at `MAX_LOTTO_TICKETS_PER_ROUND` tickets.
+fn validate_numbers(
+  game: &Game,
+  ticket_count: u32,
+  numbers: &Option<Vec<Vec<u8>>>,
+) -> Result<Vec<Vec<u8>>, ContractError> {
+  let (numbers_per_ticket, number_range, numbers) = match (&game.selection, numbers) {
+    (
+      WinnerSelection::Lotto {
+        numbers_per_ticket,
+        number_range,
+        ..
+      },
+      Some(numbers),
+    ) if numbers.len() == ticket_count as usize => (*numbers_per_ticket, *number_range, numbers),
+    (WinnerSelection::Lotto { .. }, _) | (_, Some(_)) => return Err(ContractError::InvalidNumbers {}),
+    (_, None) => return Ok(vec![]),
   };
 
-  Ok(response)
+  if game.ticket_count as u64 + ticket_count as u64 > MAX_LOTTO_TICKETS_PER_ROUND as u64 {
+    return Err(ContractError::ExceededMaxLottoTickets {
+      max_ticket_count: MAX_LOTTO_TICKETS_PER_ROUND,
+    });
+  }
+
+  let mut validated: Vec<Vec<u8>> = Vec::with_capacity(ticket_count as usize);
+  for ticket in numbers.iter() {
+    let mut ticket = ticket.clone();
+    ticket.sort_unstable();
+    ticket.dedup();
+    if ticket.len() != numbers_per_ticket as usize || ticket.iter().any(|n| *n == 0 || *n > number_range) {
+      return Err(ContractError::InvalidNumbers {});
+    }
+    validated.push(ticket);
+  }
+  Ok(validated)
+}
+
+/// Add a payment for tickets to the round's pot of the asset paid with and to
+/// the buyer's payments, to be refunded if the round is canceled.
+pub(crate) fn record_payment(
+  storage: &mut dyn Storage,
+  round: u64,
+  buyer: &Addr,
+  asset: &Asset,
+  amount: Uint128,
+) -> StdResult<()> {
+  POTS.update(storage, (round, asset.key()), |pot| -> StdResult<_> {
+    Ok(pot.unwrap_or_default() + amount)
+  })?;
+  PAYMENTS.update(storage, (round, buyer.clone(), asset.key()), |paid| -> StdResult<_> {
+    Ok(paid.unwrap_or_default() + amount)
+  })?;
+  Ok(())
+}
+
+/// Notify the game's stats registry of a player's ticket purchase.
+pub(crate) fn notify_purchase(
+  game: &Game,
+  buyer: &Addr,
+  ticket_count: u32,
+  asset: &Asset,
+  amount: Uint128,
+) -> StdResult<Vec<SubMsg>> {
+  let mut update = registry::update(buyer);
+  update.ticket_count = ticket_count;
+  update.spent = vec![AssetAmount {
+    asset: asset.clone(),
+    amount,
+  }];
+  registry::notify(game, vec![update])
 }
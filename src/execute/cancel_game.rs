@@ -0,0 +1,61 @@
+use crate::constants::CANCEL_GRACE_PERIOD_SECONDS;
+use crate::error::ContractError;
+use crate::execute::seed_jackpot::{carry_over, jackpot_destination};
+use crate::registry;
+use crate::state::{load_jackpot, Game, GameStatus, GAME};
+use cosmwasm_std::{attr, CosmosMsg, DepsMut, Env, MessageInfo, Response};
+
+/// Cancel an active game, allowing each player to claim a refund. The owner
+/// may cancel at any time. Anyone else may cancel only once the game's
+/// `ends_after` time plus a grace period has elapsed, so that a game which
+/// never reaches its funding threshold doesn't lock up players' funds forever.
+/// A closed game may be canceled by anyone once the entropy it's waiting on,
+/// such as a committer's reveal, is overdue. Whatever jackpot was seeded into
+/// the round is carried over as if nobody had won it.
+pub fn execute_cancel_game(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let mut game: Game = GAME.load(deps.storage)?;
+
+  match game.status {
+    GameStatus::ACTIVE => {
+      if info.sender != game.owner {
+        match game.ends_after {
+          Some(ends_after) if env.block.time > ends_after.plus_seconds(CANCEL_GRACE_PERIOD_SECONDS) => {},
+          _ => return Err(ContractError::NotAuthorized {}),
+        }
+      }
+    },
+    GameStatus::CLOSED => {
+      // once sales have closed, not even the owner may cancel the game until
+      // the entropy it's waiting on is overdue; otherwise, a committer could
+      // abort any draw whose outcome they don't like.
+      match game.entropy_deadline() {
+        Some(deadline) if env.block.time > deadline => {},
+        _ => return Err(ContractError::NotAuthorized {}),
+      }
+    },
+    _ => return Err(ContractError::NotActive {}),
+  }
+
+  game.status = GameStatus::CANCELED;
+  game.canceled_at = Some(env.block.time);
+  game.canceled_by = Some(info.sender.clone());
+
+  GAME.save(deps.storage, &game)?;
+
+  let mut messages: Vec<CosmosMsg> = vec![];
+  if let Some(destination) = jackpot_destination(&game) {
+    let seeded = load_jackpot(deps.storage, &game)?;
+    messages.extend(carry_over(deps.storage, &game, &destination, &seeded)?);
+  }
+
+  Ok(
+    Response::new()
+      .add_messages(messages)
+      .add_submessages(registry::notify_status(&game, vec![])?)
+      .add_attributes(vec![attr("action", "cancel_game"), attr("canceled_by", info.sender)]),
+  )
+}
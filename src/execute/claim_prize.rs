@@ -1,73 +1,117 @@
+use crate::asset::AssetAmount;
 use crate::error::ContractError;
-use crate::state::{Game, GameStatus, GAME, WINNERS};
-use cosmwasm_std::{
-  attr, to_binary, BankMsg, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, SubMsg, Uint128, WasmMsg,
-};
-use cw20::Cw20ExecuteMsg;
+use crate::registry;
+use crate::state::{find_winnings, load_round, winners, Game, GameStatus};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response, SubMsg, Uint128};
+use std::collections::BTreeMap;
 
+/// Claim prizes won by the sender, either at the given positions of the given
+/// round, defaulting to the current one, or, if no positions are given,
+/// wherever the sender is owed a prize in the given round or in every round.
 pub fn execute_claim_prize(
   deps: DepsMut,
   _env: Env,
   info: MessageInfo,
-  positions: &Vec<u32>,
+  positions: Option<Vec<u32>>,
+  round: Option<u64>,
 ) -> Result<Response, ContractError> {
-  let game: Game = GAME.load(deps.storage)?;
+  let current: Game = load_round(deps.storage, None)?;
 
-  // abort if the game is still active
-  if game.status != GameStatus::ENDED {
-    return Err(ContractError::NotAuthorized {});
+  // positions to claim, grouped by round
+  let mut targets: BTreeMap<u64, Vec<u32>> = BTreeMap::new();
+  match positions {
+    Some(positions) => {
+      let round = round.unwrap_or(current.round);
+      targets.insert(round, positions);
+    },
+    None => {
+      for ((round, position), winner) in find_winnings(deps.storage, &info.sender, round)?.into_iter() {
+        let game = load_round(deps.storage, Some(round))?;
+        if game.status == GameStatus::ENDED && !winner.claimable(&game).is_empty() {
+          targets.entry(round).or_default().push(position);
+        }
+      }
+      if targets.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+      }
+    },
   }
 
-  // total amount claimed by the sender
-  let mut claimed_amount = Uint128::zero();
+  // total amount of each asset claimed by the sender
+  let mut claimed: Vec<AssetAmount> = current
+    .price_table()
+    .into_iter()
+    .map(|price| AssetAmount {
+      asset: price.asset,
+      amount: Uint128::zero(),
+    })
+    .collect();
 
   // iterate through all "positions" won by the sender,
   // computing the total amount to be claimed
-  for position in positions.iter() {
-    let mut winner = WINNERS.load(deps.storage, *position)?;
-    if winner.address != info.sender {
+  for (round, positions) in targets.into_iter() {
+    let game: Game = load_round(deps.storage, Some(round))?;
+
+    // abort if the game is still active
+    if game.status != GameStatus::ENDED {
       return Err(ContractError::NotAuthorized {});
     }
-    if !winner.has_claimed {
-      winner.has_claimed = true;
-      claimed_amount += winner.claim_amount;
-      WINNERS.save(deps.storage, *position, &winner)?;
+
+    for position in positions.iter() {
+      let mut winner = winners().load(deps.storage, (game.round, *position))?;
+      if winner.address != info.sender {
+        return Err(ContractError::NotAuthorized {});
+      }
+      if !winner.has_claimed {
+        winner.has_claimed = true;
+        for claim in winner.claims(&game).iter() {
+          add_claim(&mut claimed, claim);
+        }
+        winners().save(deps.storage, (game.round, *position), &winner)?;
+      } else if !winner.undelivered.is_empty() {
+        // prizes whose distribution failed, no longer counted as claimed
+        for claim in winner.undelivered.drain(..) {
+          add_claim(&mut claimed, &claim);
+        }
+        winners().save(deps.storage, (game.round, *position), &winner)?;
+      }
     }
   }
 
-  // transfer balance to the winner
-  let response = match game.cw20_token_address {
-    Some(cw20_token_address) => {
-      let transfer = Cw20ExecuteMsg::Transfer {
-        recipient: info.sender.clone().into(),
-        amount: claimed_amount,
-      };
+  // transfer balances to the winner
+  let mut messages: Vec<SubMsg> = vec![];
+  for claim in claimed.iter().filter(|claim| !claim.amount.is_zero()) {
+    messages.push(SubMsg::new(claim.asset.transfer_msg(&info.sender, claim.amount)?));
+  }
 
-      let execute_msg = WasmMsg::Execute {
-        contract_addr: cw20_token_address.clone().into(),
-        msg: to_binary(&transfer)?,
-        funds: vec![],
-      };
+  let mut update = registry::update(&info.sender);
+  update.claimed = claimed
+    .iter()
+    .filter(|claim| !claim.amount.is_zero())
+    .cloned()
+    .collect();
+  if !update.claimed.is_empty() {
+    messages.extend(registry::notify(&current, vec![update])?);
+  }
 
-      Response::new()
-        .add_submessage(SubMsg::new(execute_msg))
-        .add_attributes(vec![
-          attr("action", "claim_prize"),
-          attr("claimed_amount", claimed_amount.to_string()),
-          attr("to", info.sender.clone()),
-        ])
-    },
-    None => Response::new()
-      .add_message(CosmosMsg::Bank(BankMsg::Send {
-        to_address: info.sender.clone().into(),
-        amount: vec![Coin::new(claimed_amount.u128(), game.denom)],
-      }))
-      .add_attributes(vec![
-        attr("action", "claim_prize"),
-        attr("claimed_amount", claimed_amount.to_string()),
-        attr("to", info.sender.clone()),
-      ]),
-  };
+  Ok(Response::new().add_submessages(messages).add_attributes(vec![
+    attr("action", "claim_prize"),
+    attr("claimed_amount", claimed[0].amount.to_string()),
+    attr(
+      "claimed_amounts",
+      claimed.iter().map(|claim| claim.to_string()).collect::<Vec<String>>().join(","),
+    ),
+    attr("to", info.sender.clone()),
+  ]))
+}
 
-  Ok(response)
+/// Add a claim to the running total of its asset.
+fn add_claim(
+  totals: &mut Vec<AssetAmount>,
+  claim: &AssetAmount,
+) {
+  match totals.iter_mut().find(|total| total.asset == claim.asset) {
+    Some(total) => total.amount += claim.amount,
+    None => totals.push(claim.clone()),
+  }
 }
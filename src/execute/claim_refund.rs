@@ -0,0 +1,64 @@
+use crate::asset::AssetAmount;
+use crate::error::ContractError;
+use crate::registry;
+use crate::state::{load_round, players, Game, GameStatus, PAYMENTS};
+use cosmwasm_std::{attr, CosmosMsg, DepsMut, Env, MessageInfo, Response};
+
+/// Refund the sender the full price of every ticket they bought in a round
+/// that has been canceled, in whichever assets they paid with. Defaults to the
+/// current round.
+pub fn execute_claim_refund(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  round: Option<u64>,
+) -> Result<Response, ContractError> {
+  let game: Game = load_round(deps.storage, round)?;
+
+  // abort unless the game was canceled
+  if game.status != GameStatus::CANCELED {
+    return Err(ContractError::NotCanceled {});
+  }
+
+  let mut player = match players().may_load(deps.storage, (game.round, info.sender.clone()))? {
+    Some(player) => player,
+    None => return Err(ContractError::NotAuthorized {}),
+  };
+
+  if player.has_refunded {
+    return Err(ContractError::AlreadyRefunded {});
+  }
+
+  player.has_refunded = true;
+  players().save(deps.storage, (game.round, info.sender.clone()), &player)?;
+
+  // transfer everything the player paid back to them, in each asset
+  let mut messages: Vec<CosmosMsg> = vec![];
+  let mut update = registry::update(&info.sender);
+  update.refunded_ticket_count = player.ticket_count;
+  for price in game.price_table().into_iter() {
+    let paid = PAYMENTS
+      .may_load(deps.storage, (game.round, info.sender.clone(), price.asset.key()))?
+      .unwrap_or_default();
+    if paid.is_zero() {
+      continue;
+    }
+    messages.push(price.asset.transfer_msg(&info.sender, paid)?);
+    update.refunded.push(AssetAmount {
+      asset: price.asset,
+      amount: paid,
+    });
+  }
+  let refunds: Vec<String> = update.refunded.iter().map(|refund| refund.to_string()).collect();
+
+  Ok(
+    Response::new()
+      .add_messages(messages)
+      .add_submessages(registry::notify(&game, vec![update])?)
+      .add_attributes(vec![
+        attr("action", "claim_refund"),
+        attr("refund_amounts", refunds.join(",")),
+        attr("to", info.sender),
+      ]),
+  )
+}
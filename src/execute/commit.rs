@@ -0,0 +1,36 @@
+use crate::error::ContractError;
+use crate::state::{Commitment, Game, GameStatus, COMMITMENTS, GAME};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Commit to a secret by submitting its base64-encoded SHA-256 hash. Committers
+/// may replace their commitment up until ticket sales close.
+pub fn execute_commit(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  hash: &str,
+) -> Result<Response, ContractError> {
+  let game: Game = GAME.load(deps.storage)?;
+
+  if game.status != GameStatus::ACTIVE {
+    return Err(ContractError::NotActive {});
+  }
+  if !game.is_committer(&info.sender) {
+    return Err(ContractError::NotCommitter {});
+  }
+
+  COMMITMENTS.save(
+    deps.storage,
+    (game.round, info.sender.clone()),
+    &Commitment {
+      hash: hash.to_owned(),
+      secret: None,
+    },
+  )?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "commit"),
+    attr("committer", info.sender),
+    attr("hash", hash),
+  ]))
+}
@@ -0,0 +1,136 @@
+use crate::constants::MAX_DISTRIBUTIONS_PER_TX;
+use crate::error::ContractError;
+use crate::registry;
+use crate::state::{load_round, winners, Game, GameStatus, DISTRIBUTION_CURSORS};
+use cosmwasm_std::{
+  attr, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult,
+};
+use cw_storage_plus::Bound;
+
+/// Pay the next batch of winners of a round whose prizes are distributed
+/// automatically but had too many winners to pay when it ended. Anyone may
+/// crank the distribution.
+pub fn execute_distribute_remaining(
+  deps: DepsMut,
+  _env: Env,
+  _info: MessageInfo,
+  round: Option<u64>,
+) -> Result<Response, ContractError> {
+  let game: Game = load_round(deps.storage, round)?;
+
+  if game.status != GameStatus::ENDED || !game.auto_distribute {
+    return Err(ContractError::NothingToDistribute {});
+  }
+
+  let (submessages, has_more) = distribute(deps.storage, &game)?;
+  if submessages.is_empty() && !has_more {
+    return Err(ContractError::NothingToDistribute {});
+  }
+
+  Ok(Response::new().add_submessages(submessages).add_attributes(vec![
+    attr("action", "distribute_remaining"),
+    attr("round", game.round.to_string()),
+    attr("has_more", has_more.to_string()),
+  ]))
+}
+
+/// Send the next batch of the round's winners their prizes, marking them as
+/// claimed, and return the transfers along with whether any winners are left
+/// to pay. Each transfer replies only on error, so that a failed transfer
+/// leaves the prize for the winner to claim without blocking everyone else's.
+pub(crate) fn distribute(
+  storage: &mut dyn Storage,
+  game: &Game,
+) -> StdResult<(Vec<SubMsg>, bool)> {
+  let cursor = DISTRIBUTION_CURSORS.may_load(storage, game.round)?.unwrap_or_default();
+  let batch = winners()
+    .prefix(game.round)
+    .range(storage, Some(Bound::inclusive(cursor)), None, Order::Ascending)
+    .take(MAX_DISTRIBUTIONS_PER_TX + 1)
+    .collect::<StdResult<Vec<_>>>()?;
+  // the winner past the batch, if any, is only looked up to tell there's more
+  let has_more = batch.len() > MAX_DISTRIBUTIONS_PER_TX;
+
+  let mut transfers: Vec<SubMsg> = vec![];
+  let mut updates = vec![];
+  let mut next_cursor = cursor;
+  for (position, mut winner) in batch.into_iter().take(MAX_DISTRIBUTIONS_PER_TX) {
+    next_cursor = position + 1;
+    if winner.has_claimed {
+      continue;
+    }
+    let claims = winner.claims(game);
+    for (i, claim) in claims.iter().enumerate().filter(|(_, claim)| !claim.amount.is_zero()) {
+      transfers.push(SubMsg::reply_on_error(
+        claim.asset.transfer_msg(&winner.address, claim.amount)?,
+        transfer_reply_id(game.round, position, i),
+      ));
+    }
+    let mut update = registry::update(&winner.address);
+    update.claimed = claims.into_iter().filter(|claim| !claim.amount.is_zero()).collect();
+    updates.push(update);
+    winner.has_claimed = true;
+    winners().save(storage, (game.round, position), &winner)?;
+  }
+  DISTRIBUTION_CURSORS.save(storage, game.round, &next_cursor)?;
+
+  // the registry is told of the claims first, so that the update sent in reply
+  // to a failed transfer is applied after them
+  let mut submessages = registry::notify(game, updates)?;
+  submessages.extend(transfers);
+  Ok((submessages, has_more))
+}
+
+/// Leave a prize whose transfer failed for the winner to claim, no longer
+/// counting it as claimed in the game's stats registry.
+pub fn distribute_reply(
+  deps: DepsMut,
+  _env: Env,
+  reply: Reply,
+) -> Result<Response, ContractError> {
+  let error = match reply.result {
+    SubMsgResult::Err(error) => error,
+    SubMsgResult::Ok(_) => return Ok(Response::new()),
+  };
+  let (round, position, i) = parse_transfer_reply_id(reply.id);
+
+  let game = load_round(deps.storage, Some(round))?;
+  let mut winner = winners().load(deps.storage, (round, position))?;
+  let claim = winner
+    .claims(&game)
+    .get(i)
+    .cloned()
+    .ok_or_else(|| StdError::not_found("claim"))?;
+  winner.undelivered.push(claim.clone());
+  winners().save(deps.storage, (round, position), &winner)?;
+
+  let mut update = registry::update(&winner.address);
+  update.undelivered = vec![claim.clone()];
+
+  Ok(
+    Response::new()
+      .add_submessages(registry::notify(&game, vec![update])?)
+      .add_attributes(vec![
+        attr("action", "distribution_failed"),
+        attr("round", round.to_string()),
+        attr("position", position.to_string()),
+        attr("amount", claim.to_string()),
+        attr("error", error),
+      ]),
+  )
+}
+
+/// Reply ID of a prize transfer, packing the round into the high 32 bits, the
+/// winning position into the next 24 and the index of the winner's claim into
+/// the low 8.
+fn transfer_reply_id(
+  round: u64,
+  position: u32,
+  i: usize,
+) -> u64 {
+  (round << 32) | ((position as u64 & 0xFF_FFFF) << 8) | (i as u64 & 0xFF)
+}
+
+fn parse_transfer_reply_id(id: u64) -> (u64, u32, usize) {
+  (id >> 32, ((id >> 8) & 0xFF_FFFF) as u32, (id & 0xFF) as usize)
+}
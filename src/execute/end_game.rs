@@ -1,21 +1,29 @@
-use crate::constants::{
-  GELOTTO_ADDR, GELOTTO_ANNUAL_PRIZE_ADDR, GELOTTO_NFT_SERIES_1_REWARDS_ADDR, GELOTTO_NFT_SERIES_2_REWARDS_ADDR,
-  GELOTTO_OWNER_REWARDS_ADDR,
-};
+use crate::asset::AssetAmount;
+use crate::constants::BASIS_POINTS_DENOMINATOR;
+use crate::draw;
 use crate::error::ContractError;
-use crate::msg::WinnerSelection;
+use crate::execute::distribute_remaining::distribute;
+use crate::execute::seed_jackpot::{carry_over, jackpot_destination};
+use crate::execute::start_round::start_round;
+use crate::msg::{CarryOver, Jackpot, PrizeTier, ProxyExecuteMsg, RandomnessSource, WinnerSelection};
 use crate::random;
-use crate::random::pcg64_from_game_seed;
+use crate::registry;
 use crate::state::{
-  Game, GameStatus, Player, Winner, GAME, INDEX_2_ADDR, INDICES, ORDERS, PLAYERS, PREV_HEIGHT, WINNERS,
+  load_jackpot, load_pots, players, winners, Game, GameStatus, Player, SeedFinalization, Winner, COMMITMENTS,
+  DRAND_RANDOMNESS, GAME, ORDERS, PREV_HEIGHT, SEED_FINALIZATION,
 };
 use cosmwasm_std::{
-  attr, to_binary, Addr, BankMsg, BlockInfo, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, Storage, Uint128,
+  attr, to_binary, Addr, Binary, BlockInfo, CosmosMsg, DepsMut, Env, MessageInfo, Order, Response, Storage, Uint128,
   WasmMsg,
 };
-use cw20::Cw20ExecuteMsg;
-use std::collections::HashSet;
 
+/// End the game. For games drawn from the seed alone, this selects winners and
+/// pays out royalties right away. For games that depend on entropy from
+/// committers or drand, the first call only closes ticket sales; once every
+/// committer has revealed their secret or the drand beacon has been submitted,
+/// a second call mixes that entropy into the seed and completes the draw. For
+/// games using a randomness proxy, closing ticket sales requests randomness
+/// and the draw completes when the proxy calls back.
 pub fn execute_end_game(
   deps: DepsMut,
   env: Env,
@@ -24,162 +32,213 @@ pub fn execute_end_game(
 ) -> Result<Response, ContractError> {
   let mut game: Game = GAME.load(deps.storage)?;
 
-  authorize_and_validate(&game, &env)?;
+  match game.status {
+    GameStatus::ACTIVE => {
+      authorize_and_validate(deps.storage, &game, &env)?;
 
-  let orders = ORDERS.load(deps.storage)?;
+      // committers know their own secret, so one closing the game could try
+      // lucky phrases until the final seed favors them
+      if lucky_phrase.is_some() && game.is_committer(&info.sender) {
+        return Err(ContractError::CommitterLuckyPhrase {});
+      }
 
-  // Mark this end_game request as suspect if it's on the same block as the
-  // latest buy_tickets execution. we can't allow this because an adversary
-  // could employ a brute force attack to manipulate the PRNG seed in
-  // buy_tickets, such that end_game always results in their wallet being drawn
-  // as a winner.
-  let mut is_suspect = false;
-  if orders.len() > 1 {
-    let has_same_block_height = PREV_HEIGHT.load(deps.storage)? == env.block.height;
-    if has_same_block_height {
-      is_suspect = true;
-    }
-  }
+      // Mark this end_game request as suspect if it's on the same block as the
+      // latest buy_tickets execution. we can't allow this because an adversary
+      // could employ a brute force attack to manipulate the PRNG seed in
+      // buy_tickets, such that end_game always results in their wallet being drawn
+      // as a winner.
+      let mut is_suspect = false;
+      if game.order_count > 1 {
+        let has_same_block_height = PREV_HEIGHT.load(deps.storage)? == env.block.height;
+        if has_same_block_height {
+          is_suspect = true;
+        }
+      }
 
-  update_game(deps.storage, &mut game, &info.sender, &env.block, lucky_phrase)?;
+      if game.requires_entropy() {
+        close_game(deps.storage, &mut game, &info.sender, &env.block, lucky_phrase)?;
+        let mut response = Response::new().add_attributes(vec![
+          attr("action", "close_game"),
+          attr("closed_by", info.sender.clone()),
+        ]);
+        if let Some(round) = game.drand_round {
+          response = response.add_attribute("drand_round", round.to_string());
+        }
+        response = response.add_submessages(registry::notify_status(&game, vec![])?);
+        if let RandomnessSource::Proxy { address, .. } = &game.randomness {
+          // request randomness, to be delivered via ReceiveRandomness
+          response = response.add_message(WasmMsg::Execute {
+            contract_addr: address.clone().into(),
+            msg: to_binary(&ProxyExecuteMsg::GetNextRandomness { job_id: game.job_id() })?,
+            funds: info.funds,
+          });
+        }
+        return Ok(response);
+      }
 
-  // get total prize balance
-  let jackpot: Coin = match game.cw20_token_address {
-    Some(..) => Coin {
-      amount: Uint128::from(game.ticket_count) * game.ticket_price,
-      denom: game.denom.clone(),
+      update_game(deps.storage, &mut game, &info.sender, &env.block, lucky_phrase)?;
+      settle(deps, &env, &info.sender, &game, is_suspect)
     },
-    None => deps
-      .querier
-      .query_balance(env.contract.address.clone(), game.denom.clone())?,
-  };
+    GameStatus::CLOSED => {
+      let entropy = load_entropy(deps.storage, &game)?;
+      mix_entropy(deps.storage, &mut game, entropy)?;
+      game.status = GameStatus::ENDED;
+      game.ended_at = Some(env.block.time);
+      game.ended_by = Some(info.sender.clone());
+      GAME.save(deps.storage, &game)?;
+      settle(deps, &env, &info.sender, &game, false)
+    },
+    _ => Err(ContractError::NotActive {}),
+  }
+}
+
+/// Select winners and pay out royalties for a game whose seed is final. Each
+/// asset's pot is split the same way. For games that distribute prizes, the
+/// first batch of winners is paid right away. For games that recur, the next
+/// round is started right away. The game's registry is notified of the winners
+/// and the game's new status.
+pub(crate) fn settle(
+  deps: DepsMut,
+  env: &Env,
+  sender: &Addr,
+  game: &Game,
+  is_suspect: bool,
+) -> Result<Response, ContractError> {
+  // get total prize balance of each asset
+  let pots = load_pots(deps.storage, game)?;
 
   // if we only have one player, just refund that player and skip the whole
   // winner selection process.
-  if game.player_count == 1 {
-    if let Some(ticket_order) = ORDERS.load(deps.storage)?.get(0) {
-      let player: Player = PLAYERS.load(deps.storage, ticket_order.owner.clone())?;
-      WINNERS.save(
+  let mut response = if game.player_count == 1 {
+    if let Some(ticket_order) = ORDERS.may_load(deps.storage, (game.round, 0))? {
+      let player: Player = players().load(deps.storage, (game.round, ticket_order.owner.clone()))?;
+      winners().save(
         deps.storage,
-        0,
+        (game.round, 0),
         &Winner {
           address: ticket_order.owner.clone(),
           ticket_count: player.ticket_count,
-          claim_amount: jackpot.amount,
+          claim_amount: pots[0].amount,
+          extra_claim_amounts: pots[1..].iter().filter(|pot| !pot.amount.is_zero()).cloned().collect(),
           position: 0,
           has_claimed: true,
+          matches: None,
+          undelivered: vec![],
+          is_swept: false,
         },
       )?;
-      // transfer IBC or CW20 asset back to sole player
-      match game.cw20_token_address {
-        // transfer CW20 asset
-        Some(cw20_token_address) => {
-          let transfer = Cw20ExecuteMsg::Transfer {
-            recipient: info.sender.clone().into(),
-            amount: jackpot.amount.clone().into(),
-          };
-          let execute_msg = WasmMsg::Execute {
-            contract_addr: cw20_token_address.clone().into(),
-            msg: to_binary(&transfer)?,
-            funds: vec![],
-          };
-          return Ok(Response::new().add_message(execute_msg).add_attributes(vec![
-            attr("end_game", jackpot.amount.clone()),
-            attr("to", info.sender.clone()),
-            attr("winner_count", "1"),
-          ]));
-        },
-        None => {
-          // transfer IBC asset
-          return Ok(
-            Response::new()
-              .add_message(CosmosMsg::Bank(BankMsg::Send {
-                to_address: ticket_order.owner.clone().into(),
-                amount: vec![jackpot],
-              }))
-              .add_attributes(vec![attr("action", "end_game"), attr("winner_count", "1")]),
-          );
-        },
+      // transfer every asset back to sole player
+      let mut messages: Vec<CosmosMsg> = vec![];
+      for pot in pots.iter().filter(|pot| !pot.amount.is_zero()) {
+        messages.push(pot.asset.transfer_msg(&ticket_order.owner, pot.amount)?);
+      }
+      // nobody can win a jackpot without a draw
+      if let Some(destination) = jackpot_destination(game) {
+        let seeded = load_jackpot(deps.storage, game)?;
+        messages.extend(carry_over(deps.storage, game, &destination, &seeded)?);
       }
+      Response::new().add_messages(messages).add_attributes(vec![
+        attr("action", "end_game"),
+        attr("to", ticket_order.owner.clone()),
+        attr("winner_count", "1"),
+      ])
     } else {
-      return Ok(Response::new().add_attributes(vec![attr("action", "end_game"), attr("winner_count", "0")]));
+      Response::new().add_attributes(vec![attr("action", "end_game"), attr("winner_count", "0")])
     }
   } else {
-    let game_admin = game.owner.as_str();
-    let pct_gelotto: Uint128 = Uint128::from(25u128);
-    let pct_gelotto_annual_grand_prize: Uint128 = Uint128::from(35u128);
-    let pct_nft_series_1_rewards: Uint128 = Uint128::from(10u128);
-    let pct_nft_series_2_rewards: Uint128 = Uint128::from(10u128);
-    let pct_admin: Uint128 = Uint128::from(10u128);
-    let pct_owner_rewards: Uint128 = Uint128::from(10u128);
-    let pct_total_royalties: Uint128 = pct_gelotto
-      + pct_gelotto_annual_grand_prize
-      + pct_nft_series_1_rewards
-      + pct_nft_series_2_rewards
-      + pct_admin
-      + pct_owner_rewards;
-
-    // total amount split among winning wallets. this should equal 90% of the
-    // total amount owned by the contract.
-    let winnings = jackpot
-      .amount
-      .multiply_ratio(Uint128::from(1000u128) - pct_total_royalties, Uint128::from(1000u128));
-
-    // find N winners and store in state
-    let n_winners = select_winners(&info.sender, deps.storage, &game, winnings, is_suspect)?;
-
-    let royalties = vec![
-      (GELOTTO_ADDR, pct_gelotto),
-      (GELOTTO_ANNUAL_PRIZE_ADDR, pct_gelotto_annual_grand_prize),
-      (GELOTTO_NFT_SERIES_1_REWARDS_ADDR, pct_nft_series_1_rewards),
-      (GELOTTO_NFT_SERIES_2_REWARDS_ADDR, pct_nft_series_2_rewards),
-      (GELOTTO_OWNER_REWARDS_ADDR, pct_owner_rewards),
-      (game_admin, pct_admin),
-    ];
+    let winnings = compute_winnings(game, &pots);
 
     // build response with royalty send msgs
-    let response = match game.cw20_token_address {
-      Some(cw20_token_address) => {
-        let mut wasm_transfer_msgs: Vec<WasmMsg> = Vec::with_capacity(4);
-        for (recipient, pct) in royalties.iter() {
-          wasm_transfer_msgs.push(WasmMsg::Execute {
-            contract_addr: cw20_token_address.clone().into(),
-            msg: to_binary(&Cw20ExecuteMsg::Transfer {
-              recipient: recipient.clone().into(),
-              amount: jackpot.amount.multiply_ratio(*pct, Uint128::from(1000u128)).into(),
-            })?,
-            funds: vec![],
-          });
-        }
-        Response::new().add_messages(wasm_transfer_msgs).add_attributes(vec![
-          attr("action", "end_game"),
-          attr("winner_count", n_winners.to_string()),
-        ])
-      },
-      None => {
-        let mut cosmos_send_msgs: Vec<CosmosMsg> = Vec::with_capacity(4);
-        for (recipient, pct) in royalties.iter() {
-          cosmos_send_msgs.push(CosmosMsg::Bank(BankMsg::Send {
-            to_address: recipient.clone().into(),
-            amount: vec![Coin::new(
-              jackpot.amount.multiply_ratio(*pct, Uint128::from(1000u128)).into(),
-              game.denom.clone(),
-            )],
-          }));
-        }
-        Response::new().add_messages(cosmos_send_msgs).add_attributes(vec![
-          attr("action", "end_game"),
-          attr("winner_count", n_winners.to_string()),
-        ])
-      },
-    };
+    let mut messages: Vec<CosmosMsg> = Vec::with_capacity(pots.len() * game.royalties.len());
+    for (address, royalty) in compute_royalties(game, &pots).into_iter() {
+      if royalty.amount.is_zero() {
+        continue;
+      }
+      messages.push(royalty.asset.transfer_msg(&address, royalty.amount)?);
+    }
+
+    let mut response = Response::new().add_attribute("action", "end_game");
+
+    if let WinnerSelection::Lotto { .. } = game.selection {
+      // match tickets against the drawn numbers, carrying over what isn't won
+      let (n_winners, unclaimed) = select_lotto_winners(sender, deps.storage, game, &winnings, is_suspect)?;
+      let destination = jackpot_destination(game).unwrap_or(CarryOver::NextRound {});
+      messages.extend(carry_over(deps.storage, game, &destination, &unclaimed)?);
+      response = response.add_attribute("winner_count", n_winners.to_string());
+    } else {
+      // find N winners and store in state
+      let n_winners = select_winners(sender, deps.storage, game, &winnings, is_suspect)?;
+      response = response.add_attribute("winner_count", n_winners.to_string());
+
+      if let Some(jackpot) = &game.jackpot {
+        let is_hit = settle_jackpot(deps.storage, game, jackpot, &mut messages)?;
+        response = response.add_attribute("jackpot_hit", is_hit.to_string());
+      }
+    }
+
+    response = response.add_messages(messages);
+
+    if game.auto_distribute {
+      let (submessages, has_more) = distribute(deps.storage, game)?;
+      response = response
+        .add_submessages(submessages)
+        .add_attribute("has_more", has_more.to_string());
+    }
+
+    response
+  };
+
+  // a sole player is refunded rather than credited with a win
+  let updates = match game.player_count {
+    0 | 1 => vec![],
+    _ => registry::winner_updates(deps.storage, game)?,
+  };
+  response = response.add_submessages(registry::notify_status(game, updates)?);
+
+  if game.auto_start_round {
+    let next_round = start_round(deps.storage, env, game)?;
+    return Ok(response.add_attribute("next_round", next_round.round.to_string()));
+  }
+
+  Ok(response)
+}
+
+/// Award the grand prize, i.e. the first winner's share plus the jackpot seeded
+/// into the round, if the jackpot draw hits. Otherwise, carry it over, adding
+/// any messages needed to do so.
+fn settle_jackpot(
+  storage: &mut dyn Storage,
+  game: &Game,
+  jackpot: &Jackpot,
+  messages: &mut Vec<CosmosMsg>,
+) -> Result<bool, ContractError> {
+  let is_hit = matches!(draw::draw_jackpot(game)?, Some(jackpot_draw) if jackpot_draw.is_hit);
+  let mut grand_prize = load_jackpot(storage, game)?;
 
-    Ok(response)
+  if let Some(mut winner) = winners().may_load(storage, (game.round, 0))? {
+    for claim in winner.claims(game).into_iter() {
+      if let Some(prize) = grand_prize.iter_mut().find(|prize| prize.asset == claim.asset) {
+        prize.amount += claim.amount;
+      }
+    }
+    if is_hit {
+      winner.set_claims(grand_prize);
+      winners().save(storage, (game.round, 0), &winner)?;
+      return Ok(true);
+    }
+    // the first winner was drawn but walks away empty-handed
+    winner.set_claims(vec![]);
+    winner.has_claimed = true;
+    winners().save(storage, (game.round, 0), &winner)?;
   }
+
+  messages.extend(carry_over(storage, game, &jackpot.carry_over, &grand_prize)?);
+  Ok(is_hit)
 }
 
 /// Is the game in a valid state to be ended?
-fn authorize_and_validate(
+pub(crate) fn authorize_and_validate(
+  storage: &dyn Storage,
   game: &Game,
   env: &Env,
 ) -> Result<(), ContractError> {
@@ -201,9 +260,78 @@ fn authorize_and_validate(
       return Err(ContractError::NotAuthorized {});
     }
   }
+  // every committer must have committed to a secret before sales can close
+  if let RandomnessSource::CommitReveal { committers, .. } = &game.randomness {
+    for committer in committers.iter() {
+      if !COMMITMENTS.has(storage, (game.round, committer.clone())) {
+        return Err(ContractError::MissingCommitment {
+          committer: committer.clone(),
+        });
+      }
+    }
+  }
   Ok(())
 }
 
+/// Close the game to new ticket orders, pending entropy from committers, a
+/// drand beacon or a randomness proxy.
+fn close_game(
+  storage: &mut dyn Storage,
+  game: &mut Game,
+  sender: &Addr,
+  block: &BlockInfo,
+  lucky_phrase: &Option<String>,
+) -> Result<(), ContractError> {
+  game.status = GameStatus::CLOSED;
+  finalize_seed(storage, game, sender, block, lucky_phrase)?;
+  game.closed_at = Some(block.time);
+  if let RandomnessSource::Drand {
+    genesis_time,
+    period_seconds,
+    ..
+  } = game.randomness
+  {
+    game.drand_round = Some(random::drand::next_round(genesis_time, period_seconds, block.time));
+  }
+  GAME.save(storage, game)?;
+  Ok(())
+}
+
+/// Return the entropy to mix into a closed game's seed: either the secret
+/// revealed by each committer, ordered by address, or the drand beacon's
+/// randomness.
+pub(crate) fn load_entropy(
+  storage: &dyn Storage,
+  game: &Game,
+) -> Result<Vec<Vec<u8>>, ContractError> {
+  let mut entropy: Vec<Vec<u8>> = vec![];
+  match game.randomness {
+    RandomnessSource::CommitReveal { .. } => {
+      for result in COMMITMENTS
+        .prefix(game.round)
+        .range(storage, None, None, Order::Ascending)
+      {
+        let (committer, commitment) = result?;
+        match commitment.secret {
+          Some(secret) => entropy.push(secret.into_bytes()),
+          None => return Err(ContractError::AwaitingReveal { committer }),
+        }
+      }
+    },
+    RandomnessSource::Drand { .. } => match DRAND_RANDOMNESS.may_load(storage, game.round)? {
+      Some(randomness) => entropy.push(randomness.to_vec()),
+      None => {
+        return Err(ContractError::AwaitingBeacon {
+          round: game.drand_round.unwrap_or_default(),
+        })
+      },
+    },
+    RandomnessSource::Proxy { .. } => return Err(ContractError::AwaitingRandomness {}),
+    RandomnessSource::Seed {} => {},
+  }
+  Ok(entropy)
+}
+
 /// Update the game's state, effectively "ending" it.
 fn update_game(
   storage: &mut dyn Storage,
@@ -213,10 +341,51 @@ fn update_game(
   lucky_phrase: &Option<String>,
 ) -> Result<(), ContractError> {
   game.status = GameStatus::ENDED;
-  game.seed = random::seed::finalize(game, sender, block.height, lucky_phrase);
-  game.ended_at = Some(block.time.clone());
+  finalize_seed(storage, game, sender, block, lucky_phrase)?;
+  game.ended_at = Some(block.time);
   game.ended_by = Some(sender.clone());
-  GAME.save(storage, &game)?;
+  GAME.save(storage, game)?;
+  Ok(())
+}
+
+/// Mix the inputs of the sender who closed the game into its seed, recording
+/// them for auditing.
+fn finalize_seed(
+  storage: &mut dyn Storage,
+  game: &mut Game,
+  sender: &Addr,
+  block: &BlockInfo,
+  lucky_phrase: &Option<String>,
+) -> Result<(), ContractError> {
+  let lucky_phrase_hash = lucky_phrase.as_ref().map(|phrase| random::seed::digest(phrase));
+  game.seed = random::seed::finalize(game, sender, block.height, &lucky_phrase_hash);
+  SEED_FINALIZATION.save(
+    storage,
+    game.round,
+    &SeedFinalization {
+      sender: sender.clone(),
+      block_height: block.height,
+      lucky_phrase_hash,
+      seed: game.seed.clone(),
+      entropy: vec![],
+      mixed_seed: None,
+    },
+  )?;
+  Ok(())
+}
+
+/// Mix entropy that became available after ticket sales closed into the game's
+/// seed, recording it for auditing.
+pub(crate) fn mix_entropy(
+  storage: &mut dyn Storage,
+  game: &mut Game,
+  entropy: Vec<Vec<u8>>,
+) -> Result<(), ContractError> {
+  game.seed = random::seed::mix(&game.seed, &entropy);
+  let mut finalization = SEED_FINALIZATION.load(storage, game.round)?;
+  finalization.entropy = entropy.into_iter().map(Binary::from).collect();
+  finalization.mixed_seed = Some(game.seed.clone());
+  SEED_FINALIZATION.save(storage, game.round, &finalization)?;
   Ok(())
 }
 
@@ -225,71 +394,202 @@ fn select_winners(
   sender: &Addr,
   storage: &mut dyn Storage,
   game: &Game,
-  total_reward: Uint128,
+  winnings: &[AssetAmount],
   is_suspect: bool,
 ) -> Result<u32, ContractError> {
-  let (n_winners, pct_split) = match game.selection.clone() {
-    WinnerSelection::Fixed {
-      winner_count,
-      max_winner_count,
-      pct_split,
-    } => {
-      let mut n_winners = std::cmp::min(game.player_count, winner_count as u32);
-      if let Some(n_max) = max_winner_count {
-        if n_max > 0 {
-          n_winners = std::cmp::min(n_max, n_winners);
-        }
-      }
-      (n_winners, pct_split.clone())
-    },
-    WinnerSelection::Percent { pct_player_count } => {
-      let n_winners = std::cmp::max(1, game.player_count * (pct_player_count as u32) / 100);
-      (n_winners, vec![])
-    },
+  let (n_winners, pct_split) = draw::winner_count(game);
+  let steps = draw::draw(storage, game, n_winners)?;
+
+  let mut n_found = 0u32;
+  for step in steps.into_iter().filter(|step| !step.is_duplicate) {
+    if step.address == *sender && is_suspect {
+      return Err(ContractError::NotAuthorized {});
+    }
+    let player = players().load(storage, (game.round, step.address.clone()))?;
+    let claim_amount = allocate_reward(game, winnings[0].amount, n_found, &pct_split);
+    let extra_claim_amounts = winnings[1..]
+      .iter()
+      .map(|pot| AssetAmount {
+        asset: pot.asset.clone(),
+        amount: allocate_reward(game, pot.amount, n_found, &pct_split),
+      })
+      .filter(|claim| !claim.amount.is_zero())
+      .collect();
+    winners().save(
+      storage,
+      (game.round, n_found),
+      &Winner {
+        address: step.address,
+        ticket_count: player.ticket_count,
+        position: n_found,
+        has_claimed: false,
+        claim_amount,
+        extra_claim_amounts,
+        matches: None,
+        undelivered: vec![],
+        is_swept: false,
+      },
+    )?;
+    n_found += 1
+  }
+
+  Ok(n_found)
+}
+
+/// Match every ticket of a lotto game against the drawn numbers and split each
+/// prize tier's share of the winnings evenly among the tickets in the tier,
+/// with any jackpot seeded into the round going to the tier matching every
+/// number. Returns the number of winners and whatever wasn't won. This is linear
+/// in the number of tickets sold, which `MAX_LOTTO_TICKETS_PER_ROUND` bounds.
+fn select_lotto_winners(
+  sender: &Addr,
+  storage: &mut dyn Storage,
+  game: &Game,
+  winnings: &[AssetAmount],
+  is_suspect: bool,
+) -> Result<(u32, Vec<AssetAmount>), ContractError> {
+  let (numbers_per_ticket, prize_tiers) = match &game.selection {
+    WinnerSelection::Lotto {
+      numbers_per_ticket,
+      prize_tiers,
+      ..
+    } => (*numbers_per_ticket, prize_tiers),
+    _ => return Ok((0, winnings.to_vec())),
   };
+  let winning_numbers = draw::draw_numbers(game)?.unwrap_or_default();
+
+  let tiers = draw::count_lotto_tiers(storage, game, prize_tiers, &winning_numbers)?;
+
+  let jackpot = load_jackpot(storage, game)?;
+  let mut unclaimed: Vec<AssetAmount> = winnings
+    .iter()
+    .zip(jackpot.iter())
+    .map(|(pot, seeded)| AssetAmount {
+      asset: pot.asset.clone(),
+      amount: pot.amount + seeded.amount,
+    })
+    .collect();
 
-  let indices = INDICES.load(storage)?;
   let mut n_found = 0u32;
-  let mut rng = pcg64_from_game_seed(&game.seed)?;
-  let mut visited: HashSet<u32> = HashSet::with_capacity(n_winners as usize);
-
-  while n_found < n_winners {
-    let i = rng.next_u64() % indices.len() as u64;
-    let address_index = indices[i as usize];
-    let already_selected = visited.contains(&address_index);
-    if !game.has_distinct_winners || !already_selected {
-      let addr = INDEX_2_ADDR.load(storage, address_index)?;
-      if addr == *sender && is_suspect {
+  for (tier, players) in prize_tiers.iter().zip(tiers) {
+    let ticket_count: u32 = players.values().sum();
+    if ticket_count == 0 {
+      continue;
+    }
+    // amount of each asset won per ticket in the tier
+    let shares: Vec<AssetAmount> = tier_pool(tier, numbers_per_ticket, winnings, &jackpot)
+      .into_iter()
+      .map(|pool| AssetAmount {
+        asset: pool.asset,
+        amount: pool.amount / Uint128::from(ticket_count),
+      })
+      .collect();
+
+    for (address, count) in players.into_iter() {
+      if address == *sender && is_suspect {
         return Err(ContractError::NotAuthorized {});
       }
-      let player = PLAYERS.load(storage, addr.clone())?;
-      let claim_amount = allocate_reward(game, total_reward, n_found, &pct_split);
-      visited.insert(address_index);
-      WINNERS.save(
-        storage,
-        n_found,
-        &Winner {
-          address: addr,
-          ticket_count: player.ticket_count,
-          position: n_found,
-          has_claimed: false,
-          claim_amount,
+      let claims: Vec<AssetAmount> = shares
+        .iter()
+        .map(|share| AssetAmount {
+          asset: share.asset.clone(),
+          amount: share.amount * Uint128::from(count),
+        })
+        .collect();
+      for (left, claim) in unclaimed.iter_mut().zip(claims.iter()) {
+        left.amount -= claim.amount;
+      }
+      let mut winner = Winner {
+        address,
+        position: n_found,
+        ticket_count: count,
+        has_claimed: false,
+        claim_amount: Uint128::zero(),
+        extra_claim_amounts: vec![],
+        matches: Some(tier.matches),
+        undelivered: vec![],
+        is_swept: false,
+      };
+      winner.set_claims(claims);
+      winners().save(storage, (game.round, n_found), &winner)?;
+      n_found += 1;
+    }
+  }
+
+  Ok((n_found, unclaimed))
+}
+
+/// Amount of each asset split evenly among the tickets of a lotto prize tier,
+/// including the jackpot for the tier matching every number.
+pub(crate) fn tier_pool(
+  tier: &PrizeTier,
+  numbers_per_ticket: u8,
+  winnings: &[AssetAmount],
+  jackpot: &[AssetAmount],
+) -> Vec<AssetAmount> {
+  winnings
+    .iter()
+    .zip(jackpot.iter())
+    .map(|(pot, seeded)| {
+      let mut pool = pot.amount.multiply_ratio(tier.pct as u128, 100u128);
+      if tier.matches == numbers_per_ticket {
+        pool += seeded.amount;
+      }
+      AssetAmount {
+        asset: pot.asset.clone(),
+        amount: pool,
+      }
+    })
+    .collect()
+}
+
+/// Amount of each asset paid to each royalty recipient, pot by pot.
+pub(crate) fn compute_royalties(
+  game: &Game,
+  pots: &[AssetAmount],
+) -> Vec<(Addr, AssetAmount)> {
+  let mut royalties: Vec<(Addr, AssetAmount)> = Vec::with_capacity(pots.len() * game.royalties.len());
+  for pot in pots.iter() {
+    for royalty in game.royalties.iter() {
+      royalties.push((
+        royalty.address.clone(),
+        AssetAmount {
+          asset: pot.asset.clone(),
+          amount: pot
+            .amount
+            .multiply_ratio(royalty.basis_points, BASIS_POINTS_DENOMINATOR),
         },
-      )?;
-      n_found += 1
+      ));
     }
   }
+  royalties
+}
 
-  Ok(n_found)
+/// Total amount of each asset split among winning wallets, i.e. whatever is
+/// left over after paying out royalties.
+pub(crate) fn compute_winnings(
+  game: &Game,
+  pots: &[AssetAmount],
+) -> Vec<AssetAmount> {
+  pots
+    .iter()
+    .map(|pot| AssetAmount {
+      asset: pot.asset.clone(),
+      amount: pot.amount.multiply_ratio(
+        BASIS_POINTS_DENOMINATOR - game.royalty_basis_points() as u128,
+        BASIS_POINTS_DENOMINATOR,
+      ),
+    })
+    .collect()
 }
 
 /// Based on a winner's position and the selection method in play, return the
 /// portion of the jackpot that the winner is entitled to claim.
-fn allocate_reward(
+pub(crate) fn allocate_reward(
   game: &Game,
   total_reward: Uint128,
   position: u32,
-  pct_split: &Vec<u8>,
+  pct_split: &[u8],
 ) -> Uint128 {
   match game.selection {
     WinnerSelection::Fixed { .. } => {
@@ -301,10 +601,13 @@ fn allocate_reward(
         Uint128::zero()
       }
     },
-    WinnerSelection::Percent { pct_player_count } => {
-      // each winner gets a fixed uniform percent of the jackpot
-      let n_winners = Uint128::from(pct_player_count as u32 * game.player_count as u32 / 100);
-      total_reward / n_winners
+    WinnerSelection::Percent { .. } => {
+      // each winner gets an equal share of the jackpot, split as many ways as
+      // there are winners drawn, which is always at least one
+      let (n_winners, _) = draw::winner_count(game);
+      total_reward / Uint128::from(n_winners)
     },
+    // lotto prizes are allocated per tier by `select_lotto_winners`
+    WinnerSelection::Lotto { .. } => Uint128::zero(),
   }
 }
@@ -1,7 +1,33 @@
 mod buy_tickets;
+mod cancel_game;
 mod claim_prize;
+mod claim_refund;
+mod commit;
+mod distribute_remaining;
 mod end_game;
+mod receive;
+mod receive_randomness;
+mod reveal;
+mod seed_jackpot;
+mod start_round;
+mod submit_beacon;
+mod sweep_unclaimed;
 
 pub use buy_tickets::execute_buy_tickets as buy_tickets;
+pub use cancel_game::execute_cancel_game as cancel_game;
 pub use claim_prize::execute_claim_prize as claim_prize;
+pub use claim_refund::execute_claim_refund as claim_refund;
+pub use commit::execute_commit as commit;
+pub use distribute_remaining::distribute_reply;
+pub use distribute_remaining::execute_distribute_remaining as distribute_remaining;
 pub use end_game::execute_end_game as end_game;
+pub(crate) use end_game::{
+  allocate_reward, authorize_and_validate, compute_royalties, compute_winnings, load_entropy, tier_pool,
+};
+pub use receive::execute_receive as receive;
+pub use receive_randomness::execute_receive_randomness as receive_randomness;
+pub use reveal::execute_reveal as reveal;
+pub use seed_jackpot::execute_seed_jackpot as seed_jackpot;
+pub use start_round::execute_start_round as start_round;
+pub use submit_beacon::execute_submit_beacon as submit_beacon;
+pub use sweep_unclaimed::execute_sweep_unclaimed as sweep_unclaimed;
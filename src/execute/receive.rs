@@ -0,0 +1,79 @@
+use crate::asset::{Asset, AssetAmount};
+use crate::error::ContractError;
+use crate::execute::buy_tickets::{notify_purchase, record_payment, record_ticket_order};
+use crate::execute::seed_jackpot::seed_jackpot;
+use crate::msg::ReceiveMsg;
+use crate::policy;
+use crate::state::{Game, GAME};
+use cosmwasm_std::{attr, from_binary, DepsMut, Env, MessageInfo, Response, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// Handle CW20 tokens sent to this contract with an embedded `ReceiveMsg`,
+/// crediting the tokens' original sender with tickets or adding the tokens to
+/// the jackpot. Any token in the game's price table is accepted.
+pub fn execute_receive(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  receive_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+  let game: Game = GAME.load(deps.storage)?;
+
+  // only accept tokens that tickets are priced in
+  let asset = Asset::Cw20 {
+    address: info.sender.clone(),
+  };
+  let ticket_price = match game.price_of(&asset) {
+    Some(price) => price,
+    None => return Err(ContractError::UnsupportedAsset {}),
+  };
+
+  let sender = deps.api.addr_validate(&receive_msg.sender)?;
+
+  match from_binary(&receive_msg.msg)? {
+    ReceiveMsg::BuyTickets {
+      ticket_count,
+      lucky_phrase,
+      numbers,
+    } => {
+      let buyer = sender;
+      policy::authorize_caller(&deps.querier, &game.caller_policy, &buyer)?;
+
+      // verify that exactly the amount owed for the tickets was sent
+      let payment_amount = ticket_price * Uint128::from(ticket_count);
+      if receive_msg.amount < payment_amount {
+        return Err(ContractError::InsufficientFunds {});
+      } else if receive_msg.amount > payment_amount {
+        return Err(ContractError::ExcessFunds {});
+      }
+
+      let game = record_ticket_order(deps.storage, &env, &buyer, ticket_count, &lucky_phrase, &numbers)?;
+      record_payment(deps.storage, game.round, &buyer, &asset, payment_amount)?;
+
+      Ok(
+        Response::new()
+          .add_submessages(notify_purchase(&game, &buyer, ticket_count, &asset, payment_amount)?)
+          .add_attributes(vec![
+            attr("action", "buy_tickets"),
+            attr("ticket_count", ticket_count.to_string()),
+            attr("asset", asset.to_string()),
+            attr("buyer", buyer),
+          ]),
+      )
+    },
+    ReceiveMsg::SeedJackpot {} => {
+      let seeded = AssetAmount {
+        asset,
+        amount: receive_msg.amount,
+      };
+      let round = seed_jackpot(deps.storage, &game, &seeded)?;
+
+      Ok(Response::new().add_attributes(vec![
+        attr("action", "seed_jackpot"),
+        attr("round", round.to_string()),
+        attr("amount", seeded.to_string()),
+        attr("sender", sender),
+      ]))
+    },
+  }
+}
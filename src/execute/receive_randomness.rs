@@ -0,0 +1,37 @@
+use crate::error::ContractError;
+use crate::execute::end_game::{mix_entropy, settle};
+use crate::msg::{NoisCallback, RandomnessSource};
+use crate::state::{Game, GameStatus, GAME};
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response};
+
+/// Handle randomness delivered by the game's randomness proxy, mixing it into
+/// the seed and completing the draw.
+pub fn execute_receive_randomness(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+  callback: &NoisCallback,
+) -> Result<Response, ContractError> {
+  let mut game: Game = GAME.load(deps.storage)?;
+
+  match &game.randomness {
+    RandomnessSource::Proxy { address, .. } if *address == info.sender => {},
+    _ => return Err(ContractError::NotAuthorized {}),
+  }
+  if game.status != GameStatus::CLOSED {
+    return Err(ContractError::NotClosed {});
+  }
+  if callback.job_id != game.job_id() {
+    return Err(ContractError::InvalidJobId {
+      job_id: callback.job_id.clone(),
+    });
+  }
+
+  mix_entropy(deps.storage, &mut game, vec![callback.randomness.to_vec()])?;
+  game.status = GameStatus::ENDED;
+  game.ended_at = Some(env.block.time);
+  game.ended_by = Some(info.sender.clone());
+  GAME.save(deps.storage, &game)?;
+
+  settle(deps, &env, &info.sender, &game, false)
+}
@@ -0,0 +1,33 @@
+use crate::error::ContractError;
+use crate::random;
+use crate::state::{Game, GameStatus, COMMITMENTS, GAME};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response};
+
+/// Reveal the secret behind a committer's commitment once ticket sales have
+/// closed. The game can be ended after every committer has revealed.
+pub fn execute_reveal(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  secret: &str,
+) -> Result<Response, ContractError> {
+  let game: Game = GAME.load(deps.storage)?;
+
+  if game.status != GameStatus::CLOSED {
+    return Err(ContractError::NotClosed {});
+  }
+
+  let mut commitment = match COMMITMENTS.may_load(deps.storage, (game.round, info.sender.clone()))? {
+    Some(commitment) => commitment,
+    None => return Err(ContractError::NotCommitter {}),
+  };
+
+  if random::seed::digest(secret) != commitment.hash {
+    return Err(ContractError::InvalidReveal {});
+  }
+
+  commitment.secret = Some(secret.to_owned());
+  COMMITMENTS.save(deps.storage, (game.round, info.sender.clone()), &commitment)?;
+
+  Ok(Response::new().add_attributes(vec![attr("action", "reveal"), attr("committer", info.sender)]))
+}
@@ -0,0 +1,122 @@
+use crate::asset::{Asset, AssetAmount};
+use crate::error::ContractError;
+use crate::msg::{CarryOver, ExecuteMsg, ReceiveMsg, WinnerSelection};
+use crate::state::{Game, GameStatus, GAME, JACKPOTS};
+use cosmwasm_std::{
+  attr, to_binary, Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdResult, Storage, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+/// Add the native funds sent to the jackpot, e.g. the carry-over from another
+/// lottery whose grand prize missed. Funds are added to the current round's
+/// jackpot or, once its draw has happened, to the next round's.
+pub fn execute_seed_jackpot(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let game: Game = GAME.load(deps.storage)?;
+
+  let coin = match info.funds.as_slice() {
+    [coin] => coin,
+    [] => return Err(ContractError::InsufficientFunds {}),
+    _ => return Err(ContractError::ExcessFunds {}),
+  };
+
+  let seeded = AssetAmount {
+    asset: Asset::Native {
+      denom: coin.denom.clone(),
+    },
+    amount: coin.amount,
+  };
+  let round = seed_jackpot(deps.storage, &game, &seeded)?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "seed_jackpot"),
+    attr("round", round.to_string()),
+    attr("amount", seeded.to_string()),
+  ]))
+}
+
+/// Add to the jackpot of the round whose draw is still to come, returning that
+/// round.
+pub(crate) fn seed_jackpot(
+  storage: &mut dyn Storage,
+  game: &Game,
+  seeded: &AssetAmount,
+) -> Result<u64, ContractError> {
+  if game.jackpot.is_none() {
+    return Err(ContractError::NoJackpot {});
+  }
+  if game.price_of(&seeded.asset).is_none() {
+    return Err(ContractError::UnsupportedAsset {});
+  }
+
+  let round = undrawn_round(game);
+  JACKPOTS.update(storage, (round, seeded.asset.key()), |jackpot| -> StdResult<_> {
+    Ok(jackpot.unwrap_or_default() + seeded.amount)
+  })?;
+
+  Ok(round)
+}
+
+/// The current round if its draw is still to come, otherwise the next round.
+pub(crate) fn undrawn_round(game: &Game) -> u64 {
+  match game.status {
+    GameStatus::ACTIVE | GameStatus::CLOSED => game.round,
+    _ => game.round + 1,
+  }
+}
+
+/// Where the jackpot seeded into a round goes if nobody wins it: wherever the
+/// game's jackpot is carried over or, in lotto games without one, whose unwon
+/// tier winnings accrue in the next round's jackpot, to the next round.
+pub(crate) fn jackpot_destination(game: &Game) -> Option<CarryOver> {
+  match (&game.jackpot, &game.selection) {
+    (Some(jackpot), _) => Some(jackpot.carry_over.clone()),
+    (None, WinnerSelection::Lotto { .. }) => Some(CarryOver::NextRound {}),
+    (None, _) => None,
+  }
+}
+
+/// Send funds that weren't paid out to the given destination, returning the
+/// messages that do so.
+pub(crate) fn carry_over(
+  storage: &mut dyn Storage,
+  game: &Game,
+  destination: &CarryOver,
+  amounts: &[AssetAmount],
+) -> StdResult<Vec<CosmosMsg>> {
+  let mut messages: Vec<CosmosMsg> = vec![];
+  for carried in amounts.iter().filter(|carried| !carried.amount.is_zero()) {
+    match (destination, &carried.asset) {
+      (CarryOver::NextRound {}, asset) => {
+        JACKPOTS.update(storage, (game.round + 1, asset.key()), |jackpot| -> StdResult<_> {
+          Ok(jackpot.unwrap_or_default() + carried.amount)
+        })?;
+      },
+      (CarryOver::Lottery { address }, Asset::Native { denom }) => {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+          contract_addr: address.clone().into(),
+          msg: to_binary(&ExecuteMsg::SeedJackpot {})?,
+          funds: vec![Coin::new(carried.amount.u128(), denom.clone())],
+        }));
+      },
+      (CarryOver::Lottery { address }, Asset::Cw20 { address: token }) => {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+          contract_addr: token.clone().into(),
+          msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: address.clone().into(),
+            amount: carried.amount,
+            msg: to_binary(&ReceiveMsg::SeedJackpot {})?,
+          })?,
+          funds: vec![],
+        }));
+      },
+      (CarryOver::Address { address }, asset) => {
+        messages.push(asset.transfer_msg(address, carried.amount)?);
+      },
+    }
+  }
+  Ok(messages)
+}
@@ -0,0 +1,68 @@
+use crate::error::ContractError;
+use crate::random;
+use crate::registry;
+use crate::state::{Game, GameStatus, GAME, ROUNDS};
+use cosmwasm_std::{attr, DepsMut, Env, MessageInfo, Response, Storage};
+
+/// Open a new round with the same config once the current round has ended or
+/// been canceled. Winners and players of past rounds can still claim their
+/// prizes and refunds by round.
+pub fn execute_start_round(
+  deps: DepsMut,
+  env: Env,
+  info: MessageInfo,
+) -> Result<Response, ContractError> {
+  let game: Game = GAME.load(deps.storage)?;
+
+  if info.sender != game.owner {
+    return Err(ContractError::NotAuthorized {});
+  }
+
+  let next_round = start_round(deps.storage, &env, &game)?;
+
+  Ok(
+    Response::new()
+      .add_submessages(registry::notify_status(&next_round, vec![])?)
+      .add_attributes(vec![
+        attr("action", "start_round"),
+        attr("round", next_round.round.to_string()),
+      ]),
+  )
+}
+
+/// Archive the current round and replace it with a fresh one.
+pub(crate) fn start_round(
+  storage: &mut dyn Storage,
+  env: &Env,
+  game: &Game,
+) -> Result<Game, ContractError> {
+  if game.status != GameStatus::ENDED && game.status != GameStatus::CANCELED {
+    return Err(ContractError::RoundInProgress {});
+  }
+
+  ROUNDS.save(storage, game.round, game)?;
+
+  let next_round = Game {
+    round: game.round + 1,
+    status: GameStatus::ACTIVE,
+    seed: random::seed::init(&game.id, env.block.height),
+    created_at_height: env.block.height,
+    ends_after: game
+      .duration_minutes
+      .map(|duration_minutes| env.block.time.plus_seconds(60 * duration_minutes as u64)),
+    player_count: 0,
+    ticket_count: 0,
+    order_count: 0,
+    closed_at: None,
+    drand_round: None,
+    ended_at: None,
+    ended_by: None,
+    canceled_at: None,
+    canceled_by: None,
+    ..game.clone()
+  };
+
+  GAME.save(storage, &next_round)?;
+
+  Ok(next_round)
+}
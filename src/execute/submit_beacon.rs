@@ -0,0 +1,36 @@
+use crate::error::ContractError;
+use crate::msg::RandomnessSource;
+use crate::random;
+use crate::state::{Game, GameStatus, DRAND_RANDOMNESS, GAME};
+use cosmwasm_std::{attr, Binary, DepsMut, Env, MessageInfo, Response};
+
+/// Submit the signature of the drand round fixed when ticket sales closed. Once
+/// verified, the round's randomness is stored for `end_game` to mix into the
+/// game's seed.
+pub fn execute_submit_beacon(
+  deps: DepsMut,
+  _env: Env,
+  info: MessageInfo,
+  signature: &Binary,
+) -> Result<Response, ContractError> {
+  let game: Game = GAME.load(deps.storage)?;
+
+  if game.status != GameStatus::CLOSED {
+    return Err(ContractError::NotClosed {});
+  }
+
+  let (pubkey, round) = match (&game.randomness, game.drand_round) {
+    (RandomnessSource::Drand { pubkey, .. }, Some(round)) => (pubkey, round),
+    _ => return Err(ContractError::NotAuthorized {}),
+  };
+
+  let randomness = random::drand::verify_beacon(pubkey.as_slice(), round, signature.as_slice())?;
+
+  DRAND_RANDOMNESS.save(deps.storage, game.round, &Binary::from(randomness))?;
+
+  Ok(Response::new().add_attributes(vec![
+    attr("action", "submit_beacon"),
+    attr("drand_round", round.to_string()),
+    attr("submitted_by", info.sender),
+  ]))
+}
@@ -0,0 +1,92 @@
+use crate::asset::AssetAmount;
+use crate::error::ContractError;
+use crate::execute::seed_jackpot::{carry_over, undrawn_round};
+use crate::msg::CarryOver;
+use crate::state::{load_round, winners, Game, GameStatus, GAME, JACKPOTS};
+use cosmwasm_std::{attr, CosmosMsg, DepsMut, Env, MessageInfo, Order, Response, StdResult, Uint128};
+
+/// Move prizes left unclaimed past the round's claim deadline, including any
+/// whose automatic distribution failed, to the deadline's destination. Anyone
+/// may sweep a round once its deadline has passed.
+pub fn execute_sweep_unclaimed(
+  deps: DepsMut,
+  env: Env,
+  _info: MessageInfo,
+  round: Option<u64>,
+) -> Result<Response, ContractError> {
+  let game: Game = load_round(deps.storage, round)?;
+
+  let claim_deadline = match &game.claim_deadline {
+    Some(claim_deadline) => claim_deadline,
+    None => return Err(ContractError::NoClaimDeadline {}),
+  };
+  match game.claim_deadline_at() {
+    Some(deadline) if game.status == GameStatus::ENDED && env.block.time > deadline => {},
+    _ => return Err(ContractError::ClaimDeadlineNotReached {}),
+  }
+
+  // total amount of each asset swept
+  let mut swept: Vec<AssetAmount> = game
+    .price_table()
+    .into_iter()
+    .map(|price| AssetAmount {
+      asset: price.asset,
+      amount: Uint128::zero(),
+    })
+    .collect();
+
+  let round_winners = winners()
+    .prefix(game.round)
+    .range(deps.storage, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<_>>>()?;
+
+  let mut positions: Vec<u32> = vec![];
+  for (position, mut winner) in round_winners.into_iter() {
+    let unclaimed = winner.claimable(&game);
+    if unclaimed.is_empty() {
+      continue;
+    }
+    for claim in unclaimed.iter() {
+      if let Some(total) = swept.iter_mut().find(|total| total.asset == claim.asset) {
+        total.amount += claim.amount;
+      }
+    }
+    winner.has_claimed = true;
+    winner.undelivered.clear();
+    winner.is_swept = true;
+    winners().save(deps.storage, (game.round, position), &winner)?;
+    positions.push(position);
+  }
+
+  if positions.is_empty() {
+    return Err(ContractError::NothingToSweep {});
+  }
+
+  let messages: Vec<CosmosMsg> = match &claim_deadline.sweep_to {
+    // the round being swept may be long past, so add to the jackpot of
+    // whichever round is next to be drawn
+    CarryOver::NextRound {} => {
+      let round = undrawn_round(&GAME.load(deps.storage)?);
+      for sweep in swept.iter().filter(|sweep| !sweep.amount.is_zero()) {
+        JACKPOTS.update(deps.storage, (round, sweep.asset.key()), |jackpot| -> StdResult<_> {
+          Ok(jackpot.unwrap_or_default() + sweep.amount)
+        })?;
+      }
+      vec![]
+    },
+    destination => carry_over(deps.storage, &game, destination, &swept)?,
+  };
+
+  Ok(Response::new().add_messages(messages).add_attributes(vec![
+    attr("action", "sweep_unclaimed"),
+    attr("round", game.round.to_string()),
+    attr(
+      "swept_positions",
+      positions.iter().map(|position| position.to_string()).collect::<Vec<String>>().join(","),
+    ),
+    attr(
+      "swept_amounts",
+      swept.iter().map(|sweep| sweep.to_string()).collect::<Vec<String>>().join(","),
+    ),
+  ]))
+}
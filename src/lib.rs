@@ -1,8 +1,13 @@
+pub mod asset;
 pub mod constants;
 pub mod contract;
+pub mod draw;
 pub mod error;
 pub mod execute;
 pub mod msg;
+pub mod odds;
+pub mod policy;
 pub mod query;
 pub mod random;
+pub mod registry;
 pub mod state;
@@ -1,5 +1,8 @@
-use crate::state::Winner;
-use cosmwasm_std::{Addr, Uint128};
+use crate::asset::AssetAmount;
+use crate::draw::{DrawStep, JackpotDraw};
+use crate::state::{Game, Royalty, SeedFinalization, Winner};
+use cosmwasm_std::{Addr, Binary, Decimal256, HexBinary, Timestamp, Uint128};
+use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -12,26 +15,184 @@ pub enum WinnerSelection {
     // Ex: [60, 30, 10] means 60% to 1st place, 30% to 2nd, 10% to 3rd
     pct_split: Vec<u8>,
     winner_count: u32,
+    // if nonzero, must be at least winner_count
     max_winner_count: Option<u32>,
   },
   Percent {
     // Ex: 2 means that max(1, 0.02 * player_count) win
     pct_player_count: u8,
   },
+  // Players pick `numbers_per_ticket` distinct numbers from 1 to `number_range`
+  // for each ticket, and as many numbers are drawn when the game ends. Each
+  // ticket wins according to how many of its numbers were drawn.
+  Lotto {
+    numbers_per_ticket: u8,
+    number_range: u8,
+    prize_tiers: Vec<PrizeTier>,
+  },
+}
+
+/// PrizeTier defines the percent of the winnings split evenly among all tickets
+/// matching exactly the given count of drawn numbers. Winnings of tiers without
+/// any such ticket, along with any jackpot seeded into the round if nobody
+/// matched every number, are carried over as per the game's jackpot or, if it
+/// has none, to its next round.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PrizeTier {
+  pub matches: u8,
+  pub pct: u8,
+}
+
+/// RandomnessSource defines where the entropy used to draw winners comes from.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RandomnessSource {
+  // Winners are drawn from the seed accumulated from ticket orders as soon as
+  // the game ends.
+  Seed {},
+  // Each committer submits the hash of a secret before ticket sales close and
+  // reveals the secret afterwards. Winners are only drawn once every secret has
+  // been revealed and mixed into the seed. If any reveal is still missing after
+  // the timeout, anyone may cancel the game, refunding all players.
+  //
+  // The draw is only unpredictable to a committer as long as another committer
+  // keeps their secret. A committer who knows every secret can compute the draw
+  // once ticket sales close, and withhold their reveal to have the game
+  // canceled if they don't like it, since no bond is at stake. Committers may
+  // not pass a lucky phrase when closing the game, which would let them try
+  // phrases until the draw favors them. Use several independent committers.
+  CommitReveal {
+    committers: Vec<Addr>,
+    reveal_timeout_minutes: u32,
+  },
+  // Winners are drawn using the randomness of the first drand round published
+  // after ticket sales close. Anyone may submit the round's signature, which is
+  // verified against the network's public key. Only networks using the
+  // unchained G1 signature scheme (e.g. quicknet) are supported. If no valid
+  // beacon is submitted within the timeout, anyone may cancel the game.
+  Drand {
+    pubkey: Binary,
+    genesis_time: u64,
+    period_seconds: u64,
+    beacon_timeout_minutes: u32,
+  },
+  // Closing ticket sales requests randomness from a proxy contract, such as
+  // Nois, which later calls back with `ReceiveRandomness`. Any funds sent with
+  // the closing `EndGame` are forwarded to the proxy to cover its fee. If the
+  // callback doesn't arrive within the timeout, anyone may cancel the game.
+  Proxy {
+    address: Addr,
+    callback_timeout_minutes: u32,
+  },
+}
+
+impl Default for RandomnessSource {
+  fn default() -> Self {
+    RandomnessSource::Seed {}
+  }
+}
+
+/// CallerPolicy defines which addresses may buy tickets and end the game.
+/// Contracts are excluded by default because they can atomically revert a
+/// transaction whose outcome doesn't go their way.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallerPolicy {
+  // any address, including contracts
+  AllowAll {},
+  // only addresses that aren't contracts
+  EoaOnly {},
+  // addresses that aren't contracts, plus the listed contracts, e.g. DAO DAO
+  // cores or multisigs
+  Allowlist { contracts: Vec<Addr> },
+}
+
+impl Default for CallerPolicy {
+  fn default() -> Self {
+    CallerPolicy::EoaOnly {}
+  }
+}
+
+/// Jackpot makes the grand prize, i.e. the first winner's share, progressive.
+/// The grand prize is only won with the given probability, drawn from the
+/// game's seed. On a miss, it's carried over instead of being paid out, along
+/// with any jackpot seeded into the round.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Jackpot {
+  // Ex: 500 means the grand prize is won 5% of the time
+  pub hit_basis_points: u16,
+  pub carry_over: CarryOver,
+}
+
+/// CarryOver defines where funds that aren't paid out to winners go.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CarryOver {
+  // the jackpot of this game's next round
+  NextRound {},
+  // the jackpot of another lottery's current round, via `SeedJackpot`
+  Lottery { address: Addr },
+  // any other address, such as a treasury
+  Address { address: Addr },
+}
+
+/// ClaimDeadline defines how long winners have to claim their prizes once a
+/// round ends, after which anyone may sweep whatever is left unclaimed to the
+/// given destination.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimDeadline {
+  pub minutes: u32,
+  pub sweep_to: CarryOver,
+}
+
+/// RoyaltyMsg defines a recipient of a fixed share of the jackpot, paid out
+/// when a game ends.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyMsg {
+  pub address: String,
+  // Ex: 250 means 2.5% of the jackpot
+  pub basis_points: u16,
+  pub label: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
   pub id: String,
+  // Owner of the game, defaulting to the instantiator. Set when a factory
+  // instantiates the game on behalf of its creator.
+  pub owner: Option<Addr>,
   pub name: Option<String>,
   pub duration_minutes: Option<u32>,
   pub denom: String,
   pub cw20_token_address: Option<Addr>,
   pub ticket_price: String,
+  // Other native denoms or CW20 tokens that tickets may be bought with, each at
+  // its own price per ticket. Every winner gets their share of each asset's pot.
+  #[serde(default)]
+  pub extra_prices: Vec<AssetAmount>,
   pub selection: WinnerSelection,
   pub has_distinct_winners: bool,
   pub max_tickets_per_player: Option<u32>,
   pub funding_threshold: Option<Uint128>,
+  pub royalties: Vec<RoyaltyMsg>,
+  pub randomness: Option<RandomnessSource>,
+  // Base64-encoded SHA-256 hash of the owner's secret, for games using
+  // commit-reveal randomness. See `RandomnessSource::CommitReveal` for what a
+  // committer can and can't influence.
+  pub commitment: Option<String>,
+  pub caller_policy: Option<CallerPolicy>,
+  // Start a new round with the same config as soon as a round ends.
+  #[serde(default)]
+  pub auto_start_round: bool,
+  // Send winners their prizes when the game ends instead of waiting for them
+  // to claim.
+  #[serde(default)]
+  pub auto_distribute: bool,
+  pub jackpot: Option<Jackpot>,
+  pub claim_deadline: Option<ClaimDeadline>,
+  // Stats registry, such as the factory, notified as players buy tickets, win
+  // and claim prizes, and as the game's status or round changes.
+  pub registry: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -43,40 +204,349 @@ pub enum ExecuteMsg {
   BuyTickets {
     ticket_count: u32,
     lucky_phrase: Option<String>,
+    // numbers picked for each ticket, in lotto games
+    numbers: Option<Vec<Vec<u8>>>,
   },
+  // claim the given positions or, if none are given, everything owed to the
+  // sender in the given round or in every round
   ClaimPrize {
-    positions: Vec<u32>,
+    positions: Option<Vec<u32>>,
+    round: Option<u64>,
+  },
+  // pay the next batch of winners of an auto-distributed round
+  DistributeRemaining {
+    round: Option<u64>,
+  },
+  // move prizes left unclaimed past the claim deadline to its destination
+  SweepUnclaimed {
+    round: Option<u64>,
+  },
+  CancelGame {},
+  ClaimRefund {
+    round: Option<u64>,
+  },
+  StartRound {},
+  SeedJackpot {},
+  Commit {
+    hash: String,
+  },
+  Reveal {
+    secret: String,
+  },
+  SubmitBeacon {
+    signature: Binary,
+  },
+  #[serde(alias = "nois_receive")]
+  ReceiveRandomness {
+    callback: NoisCallback,
+  },
+  Receive(Cw20ReceiveMsg),
+}
+
+/// Messages embedded in a CW20 `Send` to this contract, paying for tickets
+/// with the tokens sent in a single, atomic transaction.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+  BuyTickets {
+    ticket_count: u32,
+    lucky_phrase: Option<String>,
+    // numbers picked for each ticket, in lotto games
+    numbers: Option<Vec<Vec<u8>>>,
   },
+  SeedJackpot {},
+}
+
+impl ExecuteMsg {
+  /// Is the sender of this message subject to the game's caller policy? Only
+  /// buying tickets and ending the game are restricted, since those are the
+  /// steps whose outcome a contract could inspect and revert. For tickets
+  /// bought through a CW20 `Send`, the policy applies to the token sender
+  /// rather than the token contract and is checked by the receive handler.
+  pub fn is_restricted(&self) -> bool {
+    matches!(self, ExecuteMsg::BuyTickets { .. } | ExecuteMsg::EndGame { .. })
+  }
+}
+
+/// Randomness delivered by a randomness proxy contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NoisCallback {
+  pub job_id: String,
+  pub published: Timestamp,
+  pub randomness: HexBinary,
+}
+
+/// Message sent to a randomness proxy contract to request randomness.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyExecuteMsg {
+  GetNextRandomness { job_id: String },
+}
+
+/// Message sent to a stats registry to add to players' lifetime stats. It's sent
+/// with no updates when only the game's status or round has changed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryExecuteMsg {
+  RecordStats { updates: Vec<StatsUpdate> },
+}
+
+/// StatsUpdate defines what to add to a player's lifetime stats, and what to
+/// subtract from them when the player is refunded or a prize transfer fails.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsUpdate {
+  pub player: Addr,
+  #[serde(default)]
+  pub ticket_count: u32,
+  // number of winning positions
+  #[serde(default)]
+  pub win_count: u32,
+  #[serde(default)]
+  pub spent: Vec<AssetAmount>,
+  #[serde(default)]
+  pub won: Vec<AssetAmount>,
+  #[serde(default)]
+  pub claimed: Vec<AssetAmount>,
+  // tickets and payments refunded in a canceled round, to be taken back out
+  #[serde(default)]
+  pub refunded_ticket_count: u32,
+  #[serde(default)]
+  pub refunded: Vec<AssetAmount>,
+  // prizes counted as claimed whose transfer failed, to be taken back out
+  #[serde(default)]
+  pub undelivered: Vec<AssetAmount>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
-  GetWinners {},
-  GetPlayers {},
-  GetPlayerTicketCount { addr: Addr },
+  GetGame {
+    round: Option<u64>,
+  },
+  GetWinners {
+    round: Option<u64>,
+    start_after: Option<u32>,
+    limit: Option<u32>,
+  },
+  GetPlayers {
+    round: Option<u64>,
+    order_by: Option<PlayerOrder>,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+  },
+  GetPlayerTicketCount {
+    addr: Addr,
+    round: Option<u64>,
+  },
+  GetRoyalties {},
+  VerifyDraw {
+    round: Option<u64>,
+  },
+  GetSeedHistory {
+    round: Option<u64>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+  },
+  GetJackpot {
+    round: Option<u64>,
+  },
+  // everything the address can still claim, across every round
+  GetClaimable {
+    addr: Addr,
+  },
+  // the address's odds in the current round, as if it bought the given number
+  // of additional tickets
+  GetOdds {
+    addr: Addr,
+    additional_tickets: Option<u32>,
+  },
+  // what ending the current round would pay out, short of drawing winners
+  SimulateEndGame {},
+}
+
+/// PlayerOrder defines the order in which players are listed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerOrder {
+  // by address, ascending
+  Address,
+  // by ticket count, most first
+  TicketCount,
+  // by when each player bought their first ticket, earliest first
+  JoinOrder,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct MigrateMsg {}
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetGameResponse {
+  pub game: Game,
+  // amount of each asset paid for tickets in the round
+  pub pots: Vec<AssetAmount>,
+  // seconds until the round's end time, if it has one
+  pub seconds_remaining: Option<u64>,
+  pub can_end: bool,
+  // why `end_game` would currently fail, if it would
+  pub end_game_error: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GetWinnersResponse {
   pub winners: Vec<Winner>,
+  // position to pass as `start_after` to fetch the next page, if any
+  pub next: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct PlayerResponse {
   pub address: Addr,
   pub ticket_count: u32,
+  pub joined_at: u32,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GetPlayersResponse {
   pub players: Vec<PlayerResponse>,
+  // address to pass as `start_after` to fetch the next page, if any
+  pub next: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct GetTicketCountResponse {
   pub ticket_count: u32,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetRoyaltiesResponse {
+  pub royalties: Vec<Royalty>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VerifyDrawResponse {
+  pub seed: String,
+  pub ticket_count: u32,
+  pub steps: Vec<DrawStep>,
+  // true if the winners drawn by replaying the steps match stored winners. In
+  // lotto games, every ticket is instead matched against the winning numbers
+  // and each winner's tier and ticket count must match.
+  pub is_match: bool,
+  pub jackpot: Option<JackpotDraw>,
+  // numbers drawn, in lotto games
+  pub winning_numbers: Option<Vec<u8>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimablePosition {
+  pub round: u64,
+  pub position: u32,
+  pub amounts: Vec<AssetAmount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetClaimableResponse {
+  pub addr: Addr,
+  pub positions: Vec<ClaimablePosition>,
+  // total amount of each asset claimable
+  pub total: Vec<AssetAmount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetOddsResponse {
+  pub addr: Addr,
+  pub round: u64,
+  // the address's tickets, including any additional ones
+  pub ticket_count: u32,
+  pub total_ticket_count: u32,
+  // positions drawn, in games that don't match numbers
+  pub winner_count: u32,
+  // probability of winning at least one prize
+  pub win_probability: Decimal256,
+  // expected amount won of each asset in the game's price table
+  pub expected_payout: Vec<AssetAmount>,
+  // false if the odds are approximated, as in lotto games or when too many
+  // players must be accounted for to draw distinct winners
+  pub is_exact: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateEndGameResponse {
+  pub round: u64,
+  pub pots: Vec<AssetAmount>,
+  // amount of each asset paid to each royalty recipient
+  pub royalties: Vec<RoyaltyPayment>,
+  // amount of each asset split among winners, after royalties
+  pub winnings: Vec<AssetAmount>,
+  // amount of each asset seeded into the round's jackpot
+  pub jackpot: Vec<AssetAmount>,
+  // the sole player, who would be refunded every pot instead of winning
+  pub refund_to: Option<Addr>,
+  // positions drawn, in games that don't match numbers
+  pub winner_count: u32,
+  pub positions: Vec<PositionPayout>,
+  // pool of each prize tier, in lotto games
+  pub prize_tiers: Vec<PrizeTierPayout>,
+  // winnings not allocated to any position or prize tier
+  pub unallocated: Vec<AssetAmount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoyaltyPayment {
+  pub address: Addr,
+  pub label: Option<String>,
+  pub amounts: Vec<AssetAmount>,
+}
+
+/// PositionPayout is what the winner drawn at a position would be entitled to,
+/// not counting the jackpot, which the first position only wins if the jackpot
+/// draw hits.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionPayout {
+  pub position: u32,
+  pub claim_amount: Uint128,
+  pub claims: Vec<AssetAmount>,
+}
+
+/// PrizeTierPayout is the pool split among the tickets of a lotto prize tier,
+/// including the jackpot for the tier matching every number.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PrizeTierPayout {
+  pub matches: u8,
+  pub pool: Vec<AssetAmount>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetJackpotResponse {
+  pub round: u64,
+  pub config: Option<Jackpot>,
+  // amount of each asset seeded into the round's jackpot, on top of the grand
+  // prize's share of the pot
+  pub amounts: Vec<AssetAmount>,
+  // whether the grand prize was won, once the round has ended
+  pub is_hit: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SeedHistoryEntry {
+  pub seq: u64,
+  pub owner: Addr,
+  pub ticket_count: u32,
+  pub block_height: u64,
+  pub lucky_phrase_hash: Option<String>,
+  pub seed: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GetSeedHistoryResponse {
+  pub game_id: String,
+  pub round: u64,
+  pub initial_block_height: u64,
+  pub initial_seed: String,
+  // seq of the first order that chains from `initial_seed`; orders before it
+  // were placed before the contract was migrated and can't be recomputed
+  pub verifiable_from: u64,
+  pub entries: Vec<SeedHistoryEntry>,
+  pub finalization: Option<SeedFinalization>,
+  // seq to pass as `start_after` to fetch the next page, if any
+  pub next: Option<u64>,
+}
@@ -0,0 +1,285 @@
+use crate::asset::AssetAmount;
+use crate::constants::{BASIS_POINTS_DENOMINATOR, MAX_ODDS_STATES};
+use crate::draw;
+use crate::execute::{allocate_reward, tier_pool};
+use crate::msg::WinnerSelection;
+use crate::state::Game;
+use cosmwasm_std::{Decimal256, StdError, StdResult, Uint128, Uint256, Uint512};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+/// A player's chances in a round that has yet to be drawn.
+pub struct Odds {
+  // probability of winning at least one prize
+  pub win_probability: Decimal256,
+  // expected amount of each asset won, in the same order as the winnings
+  pub expected_payout: Vec<AssetAmount>,
+  // false if the odds had to be approximated
+  pub is_exact: bool,
+}
+
+/// A position drawn when the game ends: the probability that it pays out at
+/// all and the expected amount of each asset it pays.
+struct Prize {
+  pays: Decimal256,
+  amounts: Vec<Decimal256>,
+}
+
+/// Compute the odds of a player holding `ticket_count` of the game's tickets,
+/// given the ticket counts of every other player, if known, and the winnings
+/// and jackpot of each asset. A sole player is refunded rather than paid a
+/// prize.
+pub fn odds(
+  game: &Game,
+  ticket_count: u32,
+  others: Option<&[u32]>,
+  winnings: &[AssetAmount],
+  jackpot: &[AssetAmount],
+) -> StdResult<Odds> {
+  if ticket_count == 0 || game.ticket_count == 0 || game.player_count < 2 {
+    return Ok(Odds {
+      win_probability: Decimal256::zero(),
+      expected_payout: payout(winnings, vec![Decimal256::zero(); winnings.len()])?,
+      is_exact: true,
+    });
+  }
+  match &game.selection {
+    WinnerSelection::Lotto { .. } => lotto_odds(game, ticket_count, winnings, jackpot),
+    _ => {
+      let prizes = prizes(game, winnings, jackpot);
+      if let (true, Some(others)) = (game.has_distinct_winners, others) {
+        if let Some(odds) = distinct_odds(game, ticket_count, others, winnings, &prizes)? {
+          return Ok(odds);
+        }
+      }
+      // without distinct winners, every position is drawn independently
+      let mut odds = independent_odds(game, ticket_count, winnings, &prizes)?;
+      odds.is_exact = !game.has_distinct_winners;
+      Ok(odds)
+    },
+  }
+}
+
+/// Prize of each position drawn, with the grand prize paying the first
+/// position's share plus the jackpot only if the jackpot draw hits.
+fn prizes(
+  game: &Game,
+  winnings: &[AssetAmount],
+  jackpot: &[AssetAmount],
+) -> Vec<Prize> {
+  let (n_winners, pct_split) = draw::winner_count(game);
+  let hit = game
+    .jackpot
+    .as_ref()
+    .map(|jackpot| Decimal256::from_ratio(jackpot.hit_basis_points, BASIS_POINTS_DENOMINATOR));
+
+  (0..n_winners)
+    .map(|position| {
+      let mut amounts: Vec<Uint128> = winnings
+        .iter()
+        .map(|pot| allocate_reward(game, pot.amount, position, &pct_split))
+        .collect();
+      let mut pays = Decimal256::one();
+      if let (0, Some(hit)) = (position, hit) {
+        for (amount, seeded) in amounts.iter_mut().zip(jackpot.iter()) {
+          *amount += seeded.amount;
+        }
+        pays = hit;
+      }
+      if amounts.iter().all(|amount| amount.is_zero()) {
+        pays = Decimal256::zero();
+      }
+      Prize {
+        pays,
+        amounts: amounts
+          .into_iter()
+          .map(|amount| Decimal256::from_ratio(amount, 1u8) * pays)
+          .collect(),
+      }
+    })
+    .collect()
+}
+
+/// Odds when each position is drawn from every ticket, i.e. with replacement.
+fn independent_odds(
+  game: &Game,
+  ticket_count: u32,
+  winnings: &[AssetAmount],
+  prizes: &[Prize],
+) -> StdResult<Odds> {
+  let p = Decimal256::from_ratio(ticket_count, game.ticket_count);
+  let mut miss = Decimal256::one();
+  let mut expected = vec![Decimal256::zero(); winnings.len()];
+  for prize in prizes.iter() {
+    miss *= Decimal256::one() - p * prize.pays;
+    add_expected(&mut expected, p, prize);
+  }
+  Ok(Odds {
+    win_probability: Decimal256::one() - miss,
+    expected_payout: payout(winnings, expected)?,
+    is_exact: true,
+  })
+}
+
+/// Exact odds when winners are distinct, in which case each position is drawn
+/// from the tickets of players not drawn yet. Players are grouped by ticket
+/// count, so that the draw's state is the number of players drawn from each
+/// group, tracked along with the probability that the player hasn't been drawn
+/// yet. Returns `None` if there are too many states to track.
+fn distinct_odds(
+  game: &Game,
+  ticket_count: u32,
+  others: &[u32],
+  winnings: &[AssetAmount],
+  prizes: &[Prize],
+) -> StdResult<Option<Odds>> {
+  let mut groups: BTreeMap<u32, u32> = BTreeMap::new();
+  for count in others.iter().filter(|count| **count > 0) {
+    *groups.entry(*count).or_default() += 1;
+  }
+  let groups: Vec<(u32, u32)> = groups.into_iter().collect();
+
+  let mut states: BTreeMap<Vec<u32>, Decimal256> = BTreeMap::new();
+  states.insert(vec![0; groups.len()], Decimal256::one());
+
+  let mut win_probability = Decimal256::zero();
+  let mut expected = vec![Decimal256::zero(); winnings.len()];
+  for prize in prizes.iter() {
+    let mut next: BTreeMap<Vec<u32>, Decimal256> = BTreeMap::new();
+    let mut picked = Decimal256::zero();
+    for (drawn, q) in states.into_iter() {
+      let remaining = game.ticket_count as u64
+        - groups
+          .iter()
+          .zip(drawn.iter())
+          .map(|((count, _), n)| *count as u64 * *n as u64)
+          .sum::<u64>();
+      picked += q * Decimal256::from_ratio(ticket_count, remaining);
+      for (i, (count, size)) in groups.iter().enumerate() {
+        if drawn[i] == *size {
+          continue;
+        }
+        let p = Decimal256::from_ratio(*count as u64 * (*size - drawn[i]) as u64, remaining);
+        let mut state = drawn.clone();
+        state[i] += 1;
+        *next.entry(state).or_insert_with(Decimal256::zero) += q * p;
+        if next.len() > MAX_ODDS_STATES {
+          return Ok(None);
+        }
+      }
+    }
+    win_probability += picked * prize.pays;
+    add_expected(&mut expected, picked, prize);
+    states = next;
+  }
+
+  Ok(Some(Odds {
+    win_probability: std::cmp::min(win_probability, Decimal256::one()),
+    expected_payout: payout(winnings, expected)?,
+    is_exact: true,
+  }))
+}
+
+/// Odds of a lotto game, from the probability of a ticket landing in each prize
+/// tier. Tickets' numbers are assumed to be picked independently, so the odds
+/// are approximate.
+fn lotto_odds(
+  game: &Game,
+  ticket_count: u32,
+  winnings: &[AssetAmount],
+  jackpot: &[AssetAmount],
+) -> StdResult<Odds> {
+  let (numbers_per_ticket, number_range, prize_tiers) = match &game.selection {
+    WinnerSelection::Lotto {
+      numbers_per_ticket,
+      number_range,
+      prize_tiers,
+    } => (*numbers_per_ticket as u32, *number_range as u32, prize_tiers),
+    _ => return Err(StdError::generic_err("not a lotto game")),
+  };
+
+  let share = Decimal256::from_ratio(ticket_count, game.ticket_count);
+  let draws = binomial(number_range, numbers_per_ticket);
+  let mut any_tier = Decimal256::zero();
+  let mut expected = vec![Decimal256::zero(); winnings.len()];
+  for (i, tier) in prize_tiers.iter().enumerate() {
+    // tickets only ever land in the first tier with their match count
+    if prize_tiers[..i].iter().any(|other| other.matches == tier.matches) {
+      continue;
+    }
+    let matches = tier.matches as u32;
+    if matches > numbers_per_ticket || numbers_per_ticket - matches > number_range - numbers_per_ticket {
+      continue;
+    }
+    let ways =
+      binomial(numbers_per_ticket, matches) * binomial(number_range - numbers_per_ticket, numbers_per_ticket - matches);
+    let p = Decimal256::checked_from_ratio(to_uint256(ways)?, to_uint256(draws)?)
+      .map_err(|error| StdError::generic_err(error.to_string()))?;
+    any_tier += p;
+
+    // the player's share of the tier's pool, if any ticket lands in it
+    let is_hit = Decimal256::one() - pow(Decimal256::one() - p, game.ticket_count)?;
+    for (total, pool) in expected
+      .iter_mut()
+      .zip(tier_pool(tier, numbers_per_ticket as u8, winnings, jackpot))
+    {
+      *total += Decimal256::from_ratio(pool.amount, 1u8) * share * is_hit;
+    }
+  }
+
+  Ok(Odds {
+    win_probability: Decimal256::one() - pow(Decimal256::one() - any_tier, ticket_count)?,
+    expected_payout: payout(winnings, expected)?,
+    is_exact: false,
+  })
+}
+
+/// Add a position's expected prize, given the probability that the player is
+/// drawn for it.
+fn add_expected(
+  expected: &mut [Decimal256],
+  p: Decimal256,
+  prize: &Prize,
+) {
+  for (total, amount) in expected.iter_mut().zip(prize.amounts.iter()) {
+    *total += *amount * p;
+  }
+}
+
+/// Round expected amounts down to whole units of each asset.
+fn payout(
+  winnings: &[AssetAmount],
+  expected: Vec<Decimal256>,
+) -> StdResult<Vec<AssetAmount>> {
+  winnings
+    .iter()
+    .zip(expected)
+    .map(|(pot, amount)| {
+      Ok(AssetAmount {
+        asset: pot.asset.clone(),
+        amount: Uint128::try_from(amount.to_uint_floor())?,
+      })
+    })
+    .collect()
+}
+
+/// Number of ways to pick `k` of `n` numbers.
+fn binomial(
+  n: u32,
+  k: u32,
+) -> Uint512 {
+  (0..k).fold(Uint512::one(), |ways, i| {
+    ways * Uint512::from(n - i) / Uint512::from(i + 1)
+  })
+}
+
+fn to_uint256(n: Uint512) -> StdResult<Uint256> {
+  Uint256::try_from(n).map_err(|error| StdError::generic_err(error.to_string()))
+}
+
+fn pow(
+  base: Decimal256,
+  exp: u32,
+) -> StdResult<Decimal256> {
+  base.checked_pow(exp).map_err(StdError::overflow)
+}
@@ -0,0 +1,28 @@
+use crate::error::ContractError;
+use crate::msg::CallerPolicy;
+use cosmwasm_std::{Addr, QuerierWrapper};
+
+/// Ensure that the caller is permitted by the game's caller policy.
+pub fn authorize_caller(
+  querier: &QuerierWrapper,
+  policy: &CallerPolicy,
+  caller: &Addr,
+) -> Result<(), ContractError> {
+  let is_allowed = match policy {
+    CallerPolicy::AllowAll {} => true,
+    CallerPolicy::EoaOnly {} => !is_contract(querier, caller),
+    CallerPolicy::Allowlist { contracts } => contracts.contains(caller) || !is_contract(querier, caller),
+  };
+  if !is_allowed {
+    return Err(ContractError::CallerNotAllowed { caller: caller.clone() });
+  }
+  Ok(())
+}
+
+/// An address is a contract if the chain has contract info for it.
+fn is_contract(
+  querier: &QuerierWrapper,
+  addr: &Addr,
+) -> bool {
+  querier.query_wasm_contract_info(addr).is_ok()
+}
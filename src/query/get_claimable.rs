@@ -0,0 +1,36 @@
+use crate::asset::AssetAmount;
+use crate::msg::{ClaimablePosition, GetClaimableResponse};
+use crate::state::{find_winnings, load_round, GameStatus};
+use cosmwasm_std::{Addr, Deps, StdResult};
+
+pub fn get_claimable(
+  deps: Deps,
+  addr: Addr,
+) -> StdResult<GetClaimableResponse> {
+  let mut positions: Vec<ClaimablePosition> = vec![];
+  let mut total: Vec<AssetAmount> = vec![];
+
+  for ((round, position), winner) in find_winnings(deps.storage, &addr, None)?.into_iter() {
+    let game = load_round(deps.storage, Some(round))?;
+    if game.status != GameStatus::ENDED {
+      continue;
+    }
+    let amounts = winner.claimable(&game);
+    if amounts.is_empty() {
+      continue;
+    }
+    for amount in amounts.iter() {
+      match total.iter_mut().find(|sum| sum.asset == amount.asset) {
+        Some(sum) => sum.amount += amount.amount,
+        None => total.push(amount.clone()),
+      }
+    }
+    positions.push(ClaimablePosition {
+      round,
+      position,
+      amounts,
+    });
+  }
+
+  Ok(GetClaimableResponse { addr, positions, total })
+}
@@ -0,0 +1,35 @@
+use crate::error::ContractError;
+use crate::execute::{authorize_and_validate, load_entropy};
+use crate::msg::GetGameResponse;
+use crate::state::{load_pots, load_round, GameStatus};
+use cosmwasm_std::{Deps, Env, StdResult};
+
+/// Return a round's config and state along with its pots, the time left until
+/// its end time and, if `end_game` can't be executed right now, why not.
+pub fn get_game(
+  deps: Deps,
+  env: Env,
+  round: Option<u64>,
+) -> StdResult<GetGameResponse> {
+  let game = load_round(deps.storage, round)?;
+
+  let seconds_remaining = game
+    .ends_after
+    .map(|ends_after| ends_after.seconds().saturating_sub(env.block.time.seconds()));
+
+  // run the same checks as `end_game` for the round's current status
+  let end_game_error = match game.status {
+    GameStatus::ACTIVE => authorize_and_validate(deps.storage, &game, &env).err(),
+    GameStatus::CLOSED => load_entropy(deps.storage, &game).err(),
+    _ => Some(ContractError::NotActive {}),
+  }
+  .map(|error| error.to_string());
+
+  Ok(GetGameResponse {
+    pots: load_pots(deps.storage, &game)?,
+    seconds_remaining,
+    can_end: end_game_error.is_none(),
+    end_game_error,
+    game,
+  })
+}
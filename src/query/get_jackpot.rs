@@ -0,0 +1,28 @@
+use crate::draw;
+use crate::msg::GetJackpotResponse;
+use crate::state::{load_jackpot, load_round, GameStatus};
+use cosmwasm_std::{Deps, StdError, StdResult};
+
+/// Return the jackpot seeded into a round and, once the round has ended,
+/// whether its grand prize hit the jackpot.
+pub fn get_jackpot(
+  deps: Deps,
+  round: Option<u64>,
+) -> StdResult<GetJackpotResponse> {
+  let game = load_round(deps.storage, round)?;
+
+  let is_hit = if game.status == GameStatus::ENDED && game.player_count > 1 {
+    draw::draw_jackpot(&game)
+      .map_err(|e| StdError::generic_err(e.to_string()))?
+      .map(|jackpot_draw| jackpot_draw.is_hit)
+  } else {
+    None
+  };
+
+  Ok(GetJackpotResponse {
+    amounts: load_jackpot(deps.storage, &game)?,
+    round: game.round,
+    config: game.jackpot,
+    is_hit,
+  })
+}
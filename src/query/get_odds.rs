@@ -0,0 +1,103 @@
+use crate::constants::MAX_ODDS_PLAYERS;
+use crate::draw;
+use crate::execute::compute_winnings;
+use crate::msg::{GetOddsResponse, WinnerSelection};
+use crate::odds::odds;
+use crate::state::{load_jackpot, load_pots, load_round, players, Game, GameStatus};
+use cosmwasm_std::{Addr, Deps, Order, OverflowError, OverflowOperation, StdError, StdResult, Uint128};
+
+/// Return the address's odds of winning in the current round and its expected
+/// payout, as if it bought the given number of additional tickets.
+pub fn get_odds(
+  deps: Deps,
+  addr: Addr,
+  additional_tickets: Option<u32>,
+) -> StdResult<GetOddsResponse> {
+  let mut game: Game = load_round(deps.storage, None)?;
+  let additional_tickets = additional_tickets.unwrap_or_default();
+
+  let mut ticket_count = players()
+    .may_load(deps.storage, (game.round, addr.clone()))?
+    .map_or(0, |player| player.ticket_count);
+
+  // only exact odds of drawing distinct winners depend on everyone else's
+  // ticket counts, which aren't read past a cap
+  let others = match game.selection {
+    WinnerSelection::Lotto { .. } => None,
+    _ if game.has_distinct_winners => load_others(deps, &game, &addr)?,
+    _ => None,
+  };
+
+  // account for the additional tickets as if they had been bought
+  let mut pots = load_pots(deps.storage, &game)?;
+  if additional_tickets > 0 {
+    if ticket_count == 0 {
+      game.player_count += 1;
+    }
+    ticket_count = add_tickets(ticket_count, additional_tickets)?;
+    game.ticket_count = add_tickets(game.ticket_count, additional_tickets)?;
+    let cost = game.ticket_price.checked_mul(Uint128::from(additional_tickets))?;
+    pots[0].amount = pots[0].amount.checked_add(cost)?;
+  }
+  let winnings = compute_winnings(&game, &pots);
+  let jackpot = load_jackpot(deps.storage, &game)?;
+
+  // rounds already drawn or canceled can't be won anymore
+  let is_undrawn = matches!(game.status, GameStatus::ACTIVE | GameStatus::CLOSED);
+  let odds = odds(
+    &game,
+    if is_undrawn { ticket_count } else { 0 },
+    others.as_deref(),
+    &winnings,
+    &jackpot,
+  )?;
+
+  Ok(GetOddsResponse {
+    addr,
+    round: game.round,
+    ticket_count,
+    total_ticket_count: game.ticket_count,
+    winner_count: draw::winner_count(&game).0,
+    win_probability: odds.win_probability,
+    expected_payout: odds.expected_payout,
+    is_exact: odds.is_exact,
+  })
+}
+
+/// Ticket counts of every player other than the address, or `None` if there are
+/// more than `MAX_ODDS_PLAYERS` of them.
+fn load_others(
+  deps: Deps,
+  game: &Game,
+  addr: &Addr,
+) -> StdResult<Option<Vec<u32>>> {
+  let mut others: Vec<u32> = vec![];
+  for result in players()
+    .prefix(game.round)
+    .range(deps.storage, None, None, Order::Ascending)
+  {
+    let (address, player) = result?;
+    if address == *addr {
+      continue;
+    }
+    if others.len() == MAX_ODDS_PLAYERS {
+      return Ok(None);
+    }
+    others.push(player.ticket_count);
+  }
+  Ok(Some(others))
+}
+
+/// Add tickets to a ticket count, failing on overflow.
+fn add_tickets(
+  ticket_count: u32,
+  additional_tickets: u32,
+) -> StdResult<u32> {
+  ticket_count.checked_add(additional_tickets).ok_or_else(|| {
+    StdError::overflow(OverflowError::new(
+      OverflowOperation::Add,
+      ticket_count,
+      additional_tickets,
+    ))
+  })
+}
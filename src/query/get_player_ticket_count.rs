@@ -1,12 +1,14 @@
 use crate::msg::GetTicketCountResponse;
-use crate::state::PLAYERS;
+use crate::state::{load_round, players};
 use cosmwasm_std::{Addr, Deps, StdResult};
 
 pub fn get_player_ticket_count(
   deps: Deps,
   addr: Addr,
+  round: Option<u64>,
 ) -> StdResult<GetTicketCountResponse> {
-  match PLAYERS.load(deps.storage, addr) {
+  let game = load_round(deps.storage, round)?;
+  match players().load(deps.storage, (game.round, addr)) {
     Ok(player) => Ok(GetTicketCountResponse {
       ticket_count: player.ticket_count,
     }),
@@ -1,20 +1,84 @@
-use crate::msg::{GetPlayersResponse, PlayerResponse};
-use crate::state::PLAYERS;
-use cosmwasm_std::{Deps, Order, StdResult};
+use crate::constants::{DEFAULT_QUERY_LIMIT, MAX_QUERY_LIMIT};
+use crate::msg::{GetPlayersResponse, PlayerOrder, PlayerResponse};
+use crate::state::{load_round, players, Player};
+use cosmwasm_std::{Addr, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
 
-pub fn get_players(deps: Deps) -> StdResult<GetPlayersResponse> {
-  let mut players: Vec<PlayerResponse> = vec![];
-  PLAYERS
-    .range(deps.storage, None, None, Order::Ascending)
-    .for_each(|result| {
-      if result.is_ok() {
-        let (addr, player) = result.unwrap();
-        players.push(PlayerResponse {
-          address: addr,
-          ticket_count: player.ticket_count,
-        });
-      }
-    });
+/// Return a page of a round's players in the given order, by address unless
+/// otherwise specified.
+pub fn get_players(
+  deps: Deps,
+  round: Option<u64>,
+  order_by: Option<PlayerOrder>,
+  start_after: Option<Addr>,
+  limit: Option<u32>,
+) -> StdResult<GetPlayersResponse> {
+  let game = load_round(deps.storage, round)?;
+  let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+  let round_players = players();
 
-  Ok(GetPlayersResponse { players })
+  // player after which to resume, as keyed by the index being ranged over
+  let cursor = match start_after {
+    Some(addr) => {
+      let player = round_players.load(deps.storage, (game.round, addr.clone()))?;
+      Some((addr, player))
+    },
+    None => None,
+  };
+
+  let page: Vec<(Addr, Player)> = match order_by.unwrap_or(PlayerOrder::Address) {
+    PlayerOrder::Address => round_players
+      .prefix(game.round)
+      .range(
+        deps.storage,
+        cursor.map(|(addr, _)| Bound::exclusive(addr)),
+        None,
+        Order::Ascending,
+      )
+      .take(limit)
+      .collect::<StdResult<Vec<_>>>()?,
+    PlayerOrder::TicketCount => round_players
+      .idx
+      .ticket_count
+      .sub_prefix(game.round)
+      .range(
+        deps.storage,
+        None,
+        cursor.map(|(addr, player)| Bound::exclusive((player.ticket_count, (game.round, addr)))),
+        Order::Descending,
+      )
+      .take(limit)
+      .map(|result| result.map(|((_, addr), player)| (addr, player)))
+      .collect::<StdResult<Vec<_>>>()?,
+    PlayerOrder::JoinOrder => round_players
+      .idx
+      .joined_at
+      .sub_prefix(game.round)
+      .range(
+        deps.storage,
+        cursor.map(|(addr, player)| Bound::exclusive((player.joined_at, (game.round, addr)))),
+        None,
+        Order::Ascending,
+      )
+      .take(limit)
+      .map(|result| result.map(|((_, addr), player)| (addr, player)))
+      .collect::<StdResult<Vec<_>>>()?,
+  };
+
+  let next = match page.last() {
+    Some((addr, _)) if page.len() == limit => Some(addr.clone()),
+    _ => None,
+  };
+
+  Ok(GetPlayersResponse {
+    players: page
+      .into_iter()
+      .map(|(address, player)| PlayerResponse {
+        address,
+        ticket_count: player.ticket_count,
+        joined_at: player.joined_at,
+      })
+      .collect(),
+    next,
+  })
 }
@@ -0,0 +1,10 @@
+use crate::msg::GetRoyaltiesResponse;
+use crate::state::GAME;
+use cosmwasm_std::{Deps, StdResult};
+
+pub fn get_royalties(deps: Deps) -> StdResult<GetRoyaltiesResponse> {
+  let game = GAME.load(deps.storage)?;
+  Ok(GetRoyaltiesResponse {
+    royalties: game.royalties,
+  })
+}
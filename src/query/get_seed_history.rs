@@ -0,0 +1,65 @@
+use crate::constants::{DEFAULT_QUERY_LIMIT, MAX_QUERY_LIMIT};
+use crate::msg::{GetSeedHistoryResponse, SeedHistoryEntry};
+use crate::random;
+use crate::state::{load_round, ORDERS, SEED_CHECKPOINTS, SEED_FINALIZATION};
+use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+
+/// Return the inputs and resulting seed of each step in a round's SHA-256
+/// seed chain, from `seed::init` through every ticket order to
+/// `seed::finalize`, so that auditors can recompute it. For a round that was
+/// underway when the contract was migrated, the chain starts from the seed
+/// checkpointed at the migration instead.
+pub fn get_seed_history(
+  deps: Deps,
+  round: Option<u64>,
+  start_after: Option<u64>,
+  limit: Option<u32>,
+) -> StdResult<GetSeedHistoryResponse> {
+  let game = load_round(deps.storage, round)?;
+  let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+  let min = start_after.map(Bound::exclusive);
+
+  let mut entries: Vec<SeedHistoryEntry> = Vec::with_capacity(limit);
+  for result in ORDERS
+    .prefix(game.round)
+    .range(deps.storage, min, None, Order::Ascending)
+    .take(limit)
+  {
+    let (seq, order) = result?;
+    entries.push(SeedHistoryEntry {
+      seq,
+      owner: order.owner,
+      ticket_count: order.count,
+      block_height: order.block_height,
+      lucky_phrase_hash: order.lucky_phrase_hash,
+      seed: order.seed,
+    });
+  }
+
+  let next = match entries.last() {
+    Some(entry) if entry.seq + 1 < game.order_count => Some(entry.seq),
+    _ => None,
+  };
+
+  let (initial_seed, initial_block_height, verifiable_from) =
+    match SEED_CHECKPOINTS.may_load(deps.storage, game.round)? {
+      Some(checkpoint) => (checkpoint.seed, checkpoint.block_height, checkpoint.seq),
+      None => (
+        random::seed::init(&game.id, game.created_at_height),
+        game.created_at_height,
+        0,
+      ),
+    };
+
+  Ok(GetSeedHistoryResponse {
+    initial_seed,
+    initial_block_height,
+    verifiable_from,
+    finalization: SEED_FINALIZATION.may_load(deps.storage, game.round)?,
+    game_id: game.id,
+    round: game.round,
+    entries,
+    next,
+  })
+}
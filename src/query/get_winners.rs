@@ -1,17 +1,34 @@
+use crate::constants::{DEFAULT_QUERY_LIMIT, MAX_QUERY_LIMIT};
 use crate::msg::GetWinnersResponse;
-use crate::state::{Winner, WINNERS};
+use crate::state::{load_round, winners, Winner};
 use cosmwasm_std::{Deps, Order, StdResult};
+use cw_storage_plus::Bound;
 
-pub fn get_winners(deps: Deps) -> StdResult<GetWinnersResponse> {
-  let mut winners: Vec<Winner> = vec![];
-  WINNERS
-    .range(deps.storage, None, None, Order::Ascending)
-    .for_each(|result| {
-      if result.is_ok() {
-        let (_, winner) = result.unwrap();
-        winners.push(winner);
-      }
-    });
+/// Return a page of a round's winners, in order of position.
+pub fn get_winners(
+  deps: Deps,
+  round: Option<u64>,
+  start_after: Option<u32>,
+  limit: Option<u32>,
+) -> StdResult<GetWinnersResponse> {
+  let game = load_round(deps.storage, round)?;
+  let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).min(MAX_QUERY_LIMIT) as usize;
+  let min = start_after.map(Bound::exclusive);
 
-  Ok(GetWinnersResponse { winners })
+  let round_winners: Vec<Winner> = winners()
+    .prefix(game.round)
+    .range(deps.storage, min, None, Order::Ascending)
+    .take(limit)
+    .map(|result| result.map(|(_, winner)| winner))
+    .collect::<StdResult<Vec<_>>>()?;
+
+  let next = match round_winners.last() {
+    Some(winner) if round_winners.len() == limit => Some(winner.position),
+    _ => None,
+  };
+
+  Ok(GetWinnersResponse {
+    winners: round_winners,
+    next,
+  })
 }
@@ -1,7 +1,23 @@
+mod get_claimable;
+mod get_game;
+mod get_jackpot;
+mod get_odds;
 mod get_player_ticket_count;
 mod get_players;
+mod get_royalties;
+mod get_seed_history;
 mod get_winners;
+mod simulate_end_game;
+mod verify_draw;
 
+pub use get_claimable::get_claimable;
+pub use get_game::get_game;
+pub use get_jackpot::get_jackpot;
+pub use get_odds::get_odds;
 pub use get_player_ticket_count::get_player_ticket_count;
 pub use get_players::get_players;
+pub use get_royalties::get_royalties;
+pub use get_seed_history::get_seed_history;
 pub use get_winners::get_winners;
+pub use simulate_end_game::simulate_end_game;
+pub use verify_draw::verify_draw;
@@ -0,0 +1,117 @@
+use crate::asset::AssetAmount;
+use crate::draw;
+use crate::execute::{allocate_reward, compute_royalties, compute_winnings, tier_pool};
+use crate::msg::{PositionPayout, PrizeTierPayout, RoyaltyPayment, SimulateEndGameResponse, WinnerSelection};
+use crate::state::{load_jackpot, load_pots, load_round, Game, ORDERS};
+use cosmwasm_std::{Deps, StdResult, Uint128};
+
+/// Compute what ending the current round would pay out, as `end_game` does,
+/// without drawing winners or touching storage.
+pub fn simulate_end_game(deps: Deps) -> StdResult<SimulateEndGameResponse> {
+  let game: Game = load_round(deps.storage, None)?;
+  let pots = load_pots(deps.storage, &game)?;
+  let jackpot = load_jackpot(deps.storage, &game)?;
+
+  let mut response = SimulateEndGameResponse {
+    round: game.round,
+    pots: pots.clone(),
+    royalties: vec![],
+    winnings: pots.clone(),
+    jackpot: jackpot.clone(),
+    refund_to: None,
+    winner_count: 0,
+    positions: vec![],
+    prize_tiers: vec![],
+    unallocated: pots.iter().map(zero).collect(),
+  };
+
+  // a sole player is refunded without paying royalties
+  if game.player_count == 1 {
+    response.refund_to = ORDERS.may_load(deps.storage, (game.round, 0))?.map(|order| order.owner);
+    response.winner_count = 1;
+    return Ok(response);
+  }
+
+  let royalties = compute_royalties(&game, &pots);
+  response.royalties = game
+    .royalties
+    .iter()
+    .enumerate()
+    .map(|(i, royalty)| RoyaltyPayment {
+      address: royalty.address.clone(),
+      label: royalty.label.clone(),
+      amounts: royalties
+        .iter()
+        .skip(i)
+        .step_by(game.royalties.len())
+        .map(|(_, amount)| amount.clone())
+        .collect(),
+    })
+    .collect();
+
+  let winnings = compute_winnings(&game, &pots);
+  let mut unallocated = winnings.clone();
+
+  if let WinnerSelection::Lotto {
+    numbers_per_ticket,
+    prize_tiers,
+    ..
+  } = &game.selection
+  {
+    // whatever no ticket matches is carried over along with the jackpot
+    for (left, seeded) in unallocated.iter_mut().zip(jackpot.iter()) {
+      left.amount += seeded.amount;
+    }
+    for (i, tier) in prize_tiers.iter().enumerate() {
+      let pool = tier_pool(tier, *numbers_per_ticket, &winnings, &jackpot);
+      // tickets only ever land in the first tier with their match count
+      if prize_tiers[..i].iter().all(|other| other.matches != tier.matches) {
+        subtract(&mut unallocated, &pool);
+      }
+      response.prize_tiers.push(PrizeTierPayout {
+        matches: tier.matches,
+        pool,
+      });
+    }
+  } else if game.player_count > 0 {
+    let (n_winners, pct_split) = draw::winner_count(&game);
+    response.winner_count = n_winners;
+    for position in 0..n_winners {
+      let claims: Vec<AssetAmount> = winnings
+        .iter()
+        .map(|pot| AssetAmount {
+          asset: pot.asset.clone(),
+          amount: allocate_reward(&game, pot.amount, position, &pct_split),
+        })
+        .collect();
+      subtract(&mut unallocated, &claims);
+      response.positions.push(PositionPayout {
+        position,
+        claim_amount: claims[0].amount,
+        claims,
+      });
+    }
+  }
+
+  response.winnings = winnings;
+  response.unallocated = unallocated;
+  Ok(response)
+}
+
+fn zero(pot: &AssetAmount) -> AssetAmount {
+  AssetAmount {
+    asset: pot.asset.clone(),
+    amount: Uint128::zero(),
+  }
+}
+
+/// Take allocated amounts out of what's left, saturating at zero so that
+/// splits allocating more than the winnings still simulate.
+fn subtract(
+  left: &mut [AssetAmount],
+  allocated: &[AssetAmount],
+) {
+  for (left, allocated) in left.iter_mut().zip(allocated.iter()) {
+    left.amount = left.amount.saturating_sub(allocated.amount);
+  }
+}
@@ -0,0 +1,153 @@
+use crate::draw;
+use crate::msg::{VerifyDrawResponse, WinnerSelection};
+use crate::state::{load_round, winners, GameStatus, Winner};
+use cosmwasm_std::{Addr, Deps, Order, StdError, StdResult};
+
+/// Replay the draw performed when a round ended from its final seed, so that
+/// anyone can audit that the stored winners are the ones the PRNG produced.
+pub fn verify_draw(
+  deps: Deps,
+  round: Option<u64>,
+) -> StdResult<VerifyDrawResponse> {
+  let game = load_round(deps.storage, round)?;
+
+  if game.status != GameStatus::ENDED {
+    return Err(StdError::generic_err("game has not ended"));
+  }
+
+  // a game with a single player ends without a draw
+  let (steps, jackpot) = if game.player_count > 1 {
+    let (n_winners, _) = draw::winner_count(&game);
+    (
+      draw::draw(deps.storage, &game, n_winners).map_err(|e| StdError::generic_err(e.to_string()))?,
+      draw::draw_jackpot(&game).map_err(|e| StdError::generic_err(e.to_string()))?,
+    )
+  } else {
+    (vec![], None)
+  };
+
+  let winning_numbers = draw::draw_numbers(&game).map_err(|e| StdError::generic_err(e.to_string()))?;
+
+  let stored: Vec<Winner> = winners()
+    .prefix(game.round)
+    .range(deps.storage, None, None, Order::Ascending)
+    .map(|result| result.map(|(_, winner)| winner))
+    .collect::<StdResult<Vec<Winner>>>()?;
+
+  let is_match = match (&game.selection, &winning_numbers) {
+    _ if game.player_count <= 1 => true,
+    (WinnerSelection::Lotto { prize_tiers, .. }, Some(winning_numbers)) => {
+      // rematch every ticket against the winning numbers, listing each player's
+      // tickets in each tier in the order winners were stored
+      let tiers = draw::count_lotto_tiers(deps.storage, &game, prize_tiers, winning_numbers)?;
+      let matched: Vec<(Addr, Option<u8>, u32)> = prize_tiers
+        .iter()
+        .zip(tiers)
+        .flat_map(|(tier, players)| {
+          players
+            .into_iter()
+            .map(move |(address, count)| (address, Some(tier.matches), count))
+        })
+        .collect();
+      matched
+        == stored
+          .into_iter()
+          .map(|winner| (winner.address, winner.matches, winner.ticket_count))
+          .collect::<Vec<_>>()
+    },
+    _ => {
+      let drawn = steps.iter().filter(|step| !step.is_duplicate).map(|step| &step.address);
+      drawn.eq(stored.iter().map(|winner| &winner.address))
+    },
+  };
+
+  Ok(VerifyDrawResponse {
+    is_match,
+    seed: game.seed,
+    ticket_count: game.ticket_count,
+    steps,
+    jackpot,
+    winning_numbers,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::contract::{execute, instantiate};
+  use crate::msg::{ExecuteMsg, InstantiateMsg, PrizeTier};
+  use cosmwasm_std::coins;
+  use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+
+  #[test]
+  fn verifies_lotto_winners() {
+    let mut deps = mock_dependencies();
+    let mut env = mock_env();
+    let msg = InstantiateMsg {
+      id: "lotto".to_owned(),
+      owner: None,
+      name: None,
+      duration_minutes: None,
+      denom: "ujuno".to_owned(),
+      cw20_token_address: None,
+      ticket_price: "100".to_owned(),
+      extra_prices: vec![],
+      selection: WinnerSelection::Lotto {
+        numbers_per_ticket: 2,
+        number_range: 5,
+        prize_tiers: vec![PrizeTier { matches: 2, pct: 60 }, PrizeTier { matches: 1, pct: 40 }],
+      },
+      has_distinct_winners: false,
+      max_tickets_per_player: None,
+      funding_threshold: None,
+      royalties: vec![],
+      randomness: None,
+      commitment: None,
+      caller_policy: None,
+      auto_start_round: false,
+      auto_distribute: false,
+      jackpot: None,
+      claim_deadline: None,
+      registry: None,
+    };
+    instantiate(deps.as_mut(), env.clone(), mock_info("owner", &[]), msg).unwrap();
+
+    let tickets = [
+      ("alice", vec![vec![1, 2], vec![3, 4]]),
+      ("bob", vec![vec![1, 5], vec![2, 3]]),
+      ("carol", vec![vec![4, 5]]),
+    ];
+    for (player, numbers) in tickets.iter() {
+      let buy = ExecuteMsg::BuyTickets {
+        ticket_count: numbers.len() as u32,
+        lucky_phrase: None,
+        numbers: Some(numbers.clone()),
+      };
+      let funds = coins(100 * numbers.len() as u128, "ujuno");
+      execute(deps.as_mut(), env.clone(), mock_info(player, &funds), buy).unwrap();
+      env.block.height += 1;
+    }
+    let end = ExecuteMsg::EndGame { lucky_phrase: None };
+    execute(deps.as_mut(), env, mock_info("dave", &[]), end).unwrap();
+
+    let stored = winners()
+      .prefix(0)
+      .range(&deps.storage, None, None, Order::Ascending)
+      .collect::<StdResult<Vec<_>>>()
+      .unwrap();
+    assert!(!stored.is_empty());
+    assert!(verify_draw(deps.as_ref(), None).unwrap().is_match);
+
+    // a winner whose tier doesn't follow from the winning numbers
+    let (position, mut winner) = stored[0].clone();
+    winner.matches = winner.matches.map(|matches| 3 - matches);
+    winners().save(&mut deps.storage, (0, position), &winner).unwrap();
+    assert!(!verify_draw(deps.as_ref(), None).unwrap().is_match);
+
+    // a winner credited with tickets they don't hold in the tier
+    winner.matches = stored[0].1.matches;
+    winner.ticket_count += 1;
+    winners().save(&mut deps.storage, (0, position), &winner).unwrap();
+    assert!(!verify_draw(deps.as_ref(), None).unwrap().is_match);
+  }
+}
@@ -0,0 +1,129 @@
+use crate::error::ContractError;
+use cosmwasm_std::Timestamp;
+use drand_verify::{derive_randomness, G2PubkeyRfc, Pubkey};
+
+/// Parse a drand network's public key. Only networks using the unchained
+/// `bls-unchained-g1-rfc9380` scheme, like quicknet, are supported.
+pub fn parse_pubkey(pubkey: &[u8]) -> Result<G2PubkeyRfc, ContractError> {
+  G2PubkeyRfc::from_variable(pubkey).map_err(|_| ContractError::InvalidDrandConfig {})
+}
+
+/// Return the first drand round that will be published strictly after the
+/// given time, so that its randomness was unknown when ticket sales closed.
+pub fn next_round(
+  genesis_time: u64,
+  period_seconds: u64,
+  time: Timestamp,
+) -> u64 {
+  let now = time.seconds();
+  if now < genesis_time {
+    return 1;
+  }
+  // round 1 is published at genesis time, so the latest round at `now` is
+  // `(now - genesis_time) / period_seconds + 1`.
+  (now - genesis_time) / period_seconds + 2
+}
+
+/// Time at which the given drand round is published.
+pub fn round_time(
+  genesis_time: u64,
+  period_seconds: u64,
+  round: u64,
+) -> Timestamp {
+  Timestamp::from_seconds(genesis_time + (round - 1) * period_seconds)
+}
+
+/// Verify the signature of a drand round, returning the round's randomness.
+pub fn verify_beacon(
+  pubkey: &[u8],
+  round: u64,
+  signature: &[u8],
+) -> Result<[u8; 32], ContractError> {
+  let pk = parse_pubkey(pubkey)?;
+  match pk.verify(round, b"", signature) {
+    Ok(true) => Ok(derive_randomness(signature)),
+    _ => Err(ContractError::InvalidBeacon { round }),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use cosmwasm_std::HexBinary;
+  use sha2::{Digest, Sha256};
+
+  // recorded from https://api.drand.sh/52db9ba70e0cc0f6eaf7803dd07447a1f5477735fd3f661792ba94600c84e971/info
+  const QUICKNET_PUBKEY: &str = "83cf0f2896adee7eb8b5f01fcad3912212c437e0073e911fb90022d3e760183c8c4b450b6a0a6c3ac6a5776a2d1064510d1fec758c921cc22b0e17e63aaf4bcb5ed66304de9cf809bd274ca73bab4af5a6e9c76a4bc09e76eae8991ef5ece45a";
+  const QUICKNET_GENESIS_TIME: u64 = 1692803367;
+  const QUICKNET_PERIOD_SECONDS: u64 = 3;
+
+  // recorded from .../public/123
+  const ROUND: u64 = 123;
+  const SIGNATURE: &str =
+    "b75c69d0b72a5d906e854e808ba7e2accb1542ac355ae486d591aa9d43765482e26cd02df835d3546d23c4b13e0dfc92";
+
+  fn hex(s: &str) -> Vec<u8> {
+    HexBinary::from_hex(s).unwrap().to_vec()
+  }
+
+  #[test]
+  fn verifies_quicknet_beacon() {
+    let signature = hex(SIGNATURE);
+    let randomness = verify_beacon(&hex(QUICKNET_PUBKEY), ROUND, &signature).unwrap();
+    assert_eq!(randomness.to_vec(), Sha256::digest(&signature).to_vec());
+  }
+
+  #[test]
+  fn rejects_tampered_signature() {
+    let mut signature = hex(SIGNATURE);
+    signature[47] ^= 1;
+    assert!(matches!(
+      verify_beacon(&hex(QUICKNET_PUBKEY), ROUND, &signature),
+      Err(ContractError::InvalidBeacon { round: ROUND })
+    ));
+  }
+
+  #[test]
+  fn rejects_wrong_round() {
+    for round in [ROUND - 1, ROUND + 1] {
+      assert!(matches!(
+        verify_beacon(&hex(QUICKNET_PUBKEY), round, &hex(SIGNATURE)),
+        Err(ContractError::InvalidBeacon { .. })
+      ));
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_pubkey() {
+    assert!(matches!(
+      parse_pubkey(&[0u8; 96]),
+      Err(ContractError::InvalidDrandConfig {})
+    ));
+  }
+
+  #[test]
+  fn next_round_is_published_after_time() {
+    let next = |seconds| {
+      next_round(
+        QUICKNET_GENESIS_TIME,
+        QUICKNET_PERIOD_SECONDS,
+        Timestamp::from_seconds(seconds),
+      )
+    };
+    let published = |round| round_time(QUICKNET_GENESIS_TIME, QUICKNET_PERIOD_SECONDS, round).seconds();
+
+    assert_eq!(published(1), QUICKNET_GENESIS_TIME);
+    assert_eq!(published(ROUND), QUICKNET_GENESIS_TIME + 366);
+    assert_eq!(next(QUICKNET_GENESIS_TIME - 1), 1);
+    assert_eq!(next(QUICKNET_GENESIS_TIME), 2);
+    // round 123 is unknown until the second it's published
+    assert_eq!(next(published(ROUND) - 1), ROUND);
+    assert_eq!(next(published(ROUND)), ROUND + 1);
+
+    for seconds in QUICKNET_GENESIS_TIME - 5..QUICKNET_GENESIS_TIME + 20 {
+      let round = next(seconds);
+      assert!(published(round) > seconds);
+      assert!(round == 1 || published(round - 1) <= seconds);
+    }
+  }
+}
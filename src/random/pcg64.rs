@@ -31,6 +31,41 @@ impl Pcg64 {
     output_xsl_rr(self.state)
   }
 
+  /// Return a uniformly distributed integer in `[0, bound)`. Unlike taking
+  /// `next_u64() % bound`, this has no bias toward low values when `bound` isn't
+  /// a power of two. Uses Lemire's "nearly divisionless" method, rejecting the
+  /// few outputs that would otherwise be over-represented.
+  ///
+  /// See: https://arxiv.org/abs/1805.10941.
+  pub fn next_bounded(
+    &mut self,
+    bound: u64,
+  ) -> u64 {
+    self.next_bounded_with_raw(bound).1
+  }
+
+  /// Same as `next_bounded`, but also return the raw `next_u64` output from
+  /// which the bounded value was derived, for auditing purposes.
+  pub fn next_bounded_with_raw(
+    &mut self,
+    bound: u64,
+  ) -> (u64, u64) {
+    assert!(bound > 0, "bound must be positive");
+    let mut x = self.next_u64();
+    let mut m = u128::from(x) * u128::from(bound);
+    let mut low = m as u64;
+    if low < bound {
+      // 2^64 mod bound
+      let threshold = bound.wrapping_neg() % bound;
+      while low < threshold {
+        x = self.next_u64();
+        m = u128::from(x) * u128::from(bound);
+        low = m as u64;
+      }
+    }
+    (x, (m >> 64) as u64)
+  }
+
   #[inline]
   fn from_state_incr(
     state: u128,
@@ -76,3 +111,87 @@ fn output_xsl_rr(state: u128) -> u64 {
   let xsl = ((state >> XSHIFT) as u64) ^ (state as u64);
   xsl.rotate_right(rot)
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use sha2::{Digest, Sha256};
+
+  fn seeded(i: u64) -> Pcg64 {
+    Pcg64::from_seed(Sha256::digest(i.to_le_bytes()).into())
+  }
+
+  /// Pearson's chi-square statistic of the observed counts against a uniform
+  /// distribution.
+  fn chi_square(counts: &[u64]) -> f64 {
+    let total: u64 = counts.iter().sum();
+    let expected = total as f64 / counts.len() as f64;
+    counts
+      .iter()
+      .map(|count| (*count as f64 - expected).powi(2) / expected)
+      .sum()
+  }
+
+  // critical value of the chi-square distribution with 6 degrees of freedom at
+  // a significance level of 0.001
+  const CHI_SQUARE_6_CRITICAL: f64 = 22.458;
+
+  #[test]
+  fn bounded_is_uniform_across_seeds() {
+    let bound = 7u64;
+
+    // the first draw of each seed, as when a game draws its first winner
+    let mut first_draws = [0u64; 7];
+    for i in 0..20_000 {
+      first_draws[seeded(i).next_bounded(bound) as usize] += 1;
+    }
+    assert!(chi_square(&first_draws) < CHI_SQUARE_6_CRITICAL);
+
+    // successive draws of many seeds
+    let mut draws = [0u64; 7];
+    for i in 0..1_000 {
+      let mut rng = seeded(i);
+      for _ in 0..100 {
+        draws[rng.next_bounded(bound) as usize] += 1;
+      }
+    }
+    assert!(chi_square(&draws) < CHI_SQUARE_6_CRITICAL);
+  }
+
+  #[test]
+  fn bound_of_one_is_always_zero() {
+    for i in 0..1_000 {
+      assert_eq!(seeded(i).next_bounded(1), 0);
+    }
+  }
+
+  #[test]
+  fn rejects_over_represented_outputs() {
+    // 2^64 mod bound is just under 2^63, so about half of all outputs fall in
+    // the rejected range
+    let bound = (1u64 << 63) + 1;
+    let threshold = bound.wrapping_neg() % bound;
+    let mut n_rejected = 0;
+    for i in 0..100 {
+      let mut rng = seeded(i);
+      // replay the raw outputs up to the first one that isn't rejected
+      let mut raw = rng.clone();
+      let mut m = u128::from(raw.next_u64()) * u128::from(bound);
+      while (m as u64) < threshold {
+        m = u128::from(raw.next_u64()) * u128::from(bound);
+        n_rejected += 1;
+      }
+      let value = rng.next_bounded(bound);
+      assert!(value < bound);
+      assert_eq!(value, (m >> 64) as u64);
+      assert!(rng == raw);
+    }
+    assert!(n_rejected > 0);
+  }
+
+  #[test]
+  #[should_panic(expected = "bound must be positive")]
+  fn bound_of_zero_panics() {
+    seeded(0).next_bounded(0);
+  }
+}
@@ -19,15 +19,15 @@ pub fn update(
   owner: &Addr,
   ticket_count: u32,
   block_height: u64,
-  lucky_phrase: &Option<String>,
+  lucky_phrase_hash: &Option<String>,
 ) -> String {
   let mut sha256 = Sha256::new();
   sha256.update(game.seed.as_bytes());
   sha256.update(owner.as_bytes());
   sha256.update(ticket_count.to_le_bytes());
   sha256.update(block_height.to_le_bytes());
-  if let Some(lucky_phrase_str) = lucky_phrase {
-    sha256.update(lucky_phrase_str.as_bytes());
+  if let Some(lucky_phrase_hash_str) = lucky_phrase_hash {
+    sha256.update(lucky_phrase_hash_str.as_bytes());
   }
   let hash = sha256.finalize();
   Base64::encode_string(&hash)
@@ -37,15 +37,39 @@ pub fn finalize(
   game: &Game,
   sender: &Addr,
   block_height: u64,
-  lucky_phrase: &Option<String>,
+  lucky_phrase_hash: &Option<String>,
 ) -> String {
   let mut sha256 = Sha256::new();
   sha256.update(game.seed.as_bytes());
   sha256.update(sender.as_bytes());
   sha256.update(block_height.to_le_bytes());
-  if let Some(lucky_phrase_str) = lucky_phrase {
-    sha256.update(lucky_phrase_str.as_bytes());
+  if let Some(lucky_phrase_hash_str) = lucky_phrase_hash {
+    sha256.update(lucky_phrase_hash_str.as_bytes());
   }
   let hash = sha256.finalize();
   Base64::encode_string(&hash)
 }
+
+/// Mix entropy that only became available after ticket sales closed, like
+/// committers' secrets or a drand beacon, into a seed that can no longer be
+/// influenced by players.
+pub fn mix(
+  seed: &str,
+  entropy: &[Vec<u8>],
+) -> String {
+  let mut sha256 = Sha256::new();
+  sha256.update(seed.as_bytes());
+  for bytes in entropy.iter() {
+    sha256.update(bytes);
+  }
+  let hash = sha256.finalize();
+  Base64::encode_string(&hash)
+}
+
+/// Base64-encoded SHA-256 hash of a committer's secret or a lucky phrase.
+/// Lucky phrases are only ever mixed into the seed in hashed form, so that the
+/// seed's history can be recomputed from the hashes stored with each order.
+pub fn digest(text: &str) -> String {
+  let hash = Sha256::digest(text.as_bytes());
+  Base64::encode_string(&hash)
+}
@@ -0,0 +1,96 @@
+use crate::asset::AssetAmount;
+use crate::msg::{RegistryExecuteMsg, StatsUpdate};
+use crate::state::{winners, Game};
+use cosmwasm_std::{attr, to_binary, Addr, Order, Reply, Response, StdResult, Storage, SubMsg, SubMsgResult, WasmMsg};
+use std::collections::BTreeMap;
+
+/// Reply ID of notifications sent to a stats registry, kept clear of the IDs of
+/// prize transfers by rounds never reaching `u32::MAX`.
+pub const REPLY_ID: u64 = u64::MAX;
+
+/// Build the message notifying the game's stats registry of the given updates,
+/// if the game has a registry. The message replies only on error, so that a
+/// failing registry can't block the game.
+pub fn notify(
+  game: &Game,
+  updates: Vec<StatsUpdate>,
+) -> StdResult<Vec<SubMsg>> {
+  if updates.is_empty() {
+    return Ok(vec![]);
+  }
+  notify_status(game, updates)
+}
+
+/// Build the message notifying the game's stats registry of a change in the
+/// game's status or round along with the given updates, even if there are
+/// none, so that a registry indexing the game can refresh it.
+pub fn notify_status(
+  game: &Game,
+  updates: Vec<StatsUpdate>,
+) -> StdResult<Vec<SubMsg>> {
+  let registry = match &game.registry {
+    Some(registry) => registry,
+    None => return Ok(vec![]),
+  };
+  Ok(vec![SubMsg::reply_on_error(
+    WasmMsg::Execute {
+      contract_addr: registry.clone().into(),
+      msg: to_binary(&RegistryExecuteMsg::RecordStats { updates })?,
+      funds: vec![],
+    },
+    REPLY_ID,
+  )])
+}
+
+/// Swallow the error of a failed notification, whose updates are lost.
+pub fn reply(reply: Reply) -> Response {
+  let error = match reply.result {
+    SubMsgResult::Err(error) => error,
+    SubMsgResult::Ok(_) => return Response::new(),
+  };
+  Response::new().add_attributes(vec![attr("action", "notify_failed"), attr("error", error)])
+}
+
+/// Empty update for a player, to be filled in by the caller.
+pub fn update(player: &Addr) -> StatsUpdate {
+  StatsUpdate {
+    player: player.clone(),
+    ticket_count: 0,
+    win_count: 0,
+    spent: vec![],
+    won: vec![],
+    claimed: vec![],
+    refunded_ticket_count: 0,
+    refunded: vec![],
+    undelivered: vec![],
+  }
+}
+
+/// Credit each winner of the game's round with their winning positions and
+/// prizes. Winners drawn for a prize that came to nothing aren't credited.
+pub fn winner_updates(
+  storage: &dyn Storage,
+  game: &Game,
+) -> StdResult<Vec<StatsUpdate>> {
+  let mut updates: BTreeMap<Addr, StatsUpdate> = BTreeMap::new();
+  for result in winners()
+    .prefix(game.round)
+    .range(storage, None, None, Order::Ascending)
+  {
+    let (_, winner) = result?;
+    let claims: Vec<AssetAmount> = winner
+      .claims(game)
+      .into_iter()
+      .filter(|claim| !claim.amount.is_zero())
+      .collect();
+    if claims.is_empty() {
+      continue;
+    }
+    let entry = updates
+      .entry(winner.address.clone())
+      .or_insert_with(|| update(&winner.address));
+    entry.win_count += 1;
+    entry.won.extend(claims);
+  }
+  Ok(updates.into_values().collect())
+}
@@ -1,8 +1,18 @@
+use crate::asset::{Asset, AssetAmount};
+use crate::constants::{
+  BASIS_POINTS_DENOMINATOR, LEGACY_OWNER_ROYALTY_BASIS_POINTS, LEGACY_ROYALTIES, MAX_TOTAL_ROYALTY_BASIS_POINTS,
+};
 use crate::error::ContractError;
-use crate::msg::{InstantiateMsg, WinnerSelection};
+use crate::msg::{
+  CallerPolicy, CarryOver, ClaimDeadline, InstantiateMsg, Jackpot, PrizeTier, RandomnessSource, RoyaltyMsg,
+  WinnerSelection,
+};
 use crate::random;
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Timestamp, Uint128};
-use cw_storage_plus::{Item, Map};
+use crate::random::drand;
+use cosmwasm_std::{
+  Addr, Api, Binary, DepsMut, Env, MessageInfo, Order, StdError, StdResult, Storage, Timestamp, Uint128,
+};
+use cw_storage_plus::{Bound, Index, IndexList, IndexedMap, Item, KeyDeserialize, Map, MultiIndex};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
@@ -11,7 +21,9 @@ use std::convert::TryFrom;
 #[serde(rename_all = "snake_case")]
 pub enum GameStatus {
   ACTIVE,
+  CLOSED,
   ENDED,
+  CANCELED,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -19,20 +31,59 @@ pub struct Game {
   pub owner: Addr,
   pub name: Option<String>,
   pub id: String,
+  // sequence number of the current round, starting at 0
+  #[serde(default)]
+  pub round: u64,
   pub status: GameStatus,
   pub selection: WinnerSelection,
   pub player_count: u32,
+  pub closed_at: Option<Timestamp>,
+  pub drand_round: Option<u64>,
   pub ended_at: Option<Timestamp>,
   pub ended_by: Option<Addr>,
+  pub canceled_at: Option<Timestamp>,
+  pub canceled_by: Option<Addr>,
   pub denom: String,
   pub cw20_token_address: Option<Addr>,
   pub ticket_price: Uint128,
+  // tickets may also be bought with these assets, at their own prices
+  #[serde(default)]
+  pub extra_prices: Vec<AssetAmount>,
   pub ticket_count: u32,
+  #[serde(default)]
+  pub order_count: u64,
   pub seed: String,
+  #[serde(default)]
+  pub created_at_height: u64,
   pub ends_after: Option<Timestamp>,
+  #[serde(default)]
+  pub duration_minutes: Option<u32>,
+  #[serde(default)]
+  pub auto_start_round: bool,
+  #[serde(default)]
+  pub auto_distribute: bool,
   pub has_distinct_winners: bool,
   pub max_tickets_per_player: Option<u32>,
   pub funding_threshold: Option<Uint128>,
+  #[serde(default)]
+  pub royalties: Vec<Royalty>,
+  #[serde(default)]
+  pub randomness: RandomnessSource,
+  #[serde(default)]
+  pub caller_policy: CallerPolicy,
+  #[serde(default)]
+  pub jackpot: Option<Jackpot>,
+  #[serde(default)]
+  pub claim_deadline: Option<ClaimDeadline>,
+  #[serde(default)]
+  pub registry: Option<Addr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Royalty {
+  pub address: Addr,
+  pub basis_points: u16,
+  pub label: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -40,11 +91,55 @@ pub struct TicketOrder {
   pub owner: Addr,
   pub count: u32,
   pub cum_count: u64,
+  // the game's seed as updated by this order, with the inputs mixed into it
+  #[serde(default)]
+  pub seed: String,
+  #[serde(default)]
+  pub block_height: u64,
+  #[serde(default)]
+  pub lucky_phrase_hash: Option<String>,
+  // numbers picked for each ticket, in lotto games
+  #[serde(default)]
+  pub numbers: Vec<Vec<u8>>,
+}
+
+/// Inputs mixed into the seed when ticket sales closed, followed by any
+/// entropy that became available afterwards.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SeedFinalization {
+  pub sender: Addr,
+  pub block_height: u64,
+  pub lucky_phrase_hash: Option<String>,
+  pub seed: String,
+  pub entropy: Vec<Binary>,
+  pub mixed_seed: Option<String>,
+}
+
+/// The seed of a round as found when the contract was migrated. Orders placed
+/// before then didn't record their inputs, so the chain can only be recomputed
+/// from here on.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SeedCheckpoint {
+  // seq of the first order placed after the migration
+  pub seq: u64,
+  pub block_height: u64,
+  pub seed: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Player {
   pub ticket_count: u32,
+  #[serde(default)]
+  pub has_refunded: bool,
+  // number of players who joined the round before this one
+  #[serde(default)]
+  pub joined_at: u32,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Commitment {
+  pub hash: String,
+  pub secret: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -54,17 +149,128 @@ pub struct Winner {
   pub ticket_count: u32,
   pub has_claimed: bool,
   pub claim_amount: Uint128,
+  // share of each extra asset's pot, omitting zero amounts
+  #[serde(default)]
+  pub extra_claim_amounts: Vec<AssetAmount>,
+  // in lotto games, the prize tier won, in which case `ticket_count` is the
+  // number of the player's tickets in the tier
+  #[serde(default)]
+  pub matches: Option<u8>,
+  // prizes whose automatic distribution failed, left for the winner to claim
+  #[serde(default)]
+  pub undelivered: Vec<AssetAmount>,
+  // whether the prize was swept after going unclaimed past the deadline
+  #[serde(default)]
+  pub is_swept: bool,
+}
+
+pub struct PlayerIndexes<'a> {
+  pub ticket_count: MultiIndex<'a, (u64, u32), Player, (u64, Addr)>,
+  pub joined_at: MultiIndex<'a, (u64, u32), Player, (u64, Addr)>,
+}
+
+impl<'a> IndexList<Player> for PlayerIndexes<'a> {
+  fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Player>> + '_> {
+    let indexes: Vec<&dyn Index<Player>> = vec![&self.ticket_count, &self.joined_at];
+    Box::new(indexes.into_iter())
+  }
 }
 
+/// Players keyed by round and address, indexed within each round by ticket
+/// count and by the order in which they joined.
+pub fn players<'a>() -> IndexedMap<'a, (u64, Addr), Player, PlayerIndexes<'a>> {
+  IndexedMap::new(
+    "round_players",
+    PlayerIndexes {
+      ticket_count: MultiIndex::new(
+        |pk, player| (round_of(pk), player.ticket_count),
+        "round_players",
+        "round_players__ticket_count",
+      ),
+      joined_at: MultiIndex::new(
+        |pk, player| (round_of(pk), player.joined_at),
+        "round_players",
+        "round_players__joined_at",
+      ),
+    },
+  )
+}
+
+/// Round of a player, from the raw primary key passed to index functions.
+fn round_of(pk: &[u8]) -> u64 {
+  <(u64, Addr)>::from_slice(pk).map_or(0, |(round, _)| round)
+}
+
+pub struct WinnerIndexes<'a> {
+  // address and whether the winner is still owed anything, as 1 or 0
+  pub owed: MultiIndex<'a, (Addr, u8), Winner, (u64, u32)>,
+}
+
+impl<'a> IndexList<Winner> for WinnerIndexes<'a> {
+  fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Winner>> + '_> {
+    let indexes: Vec<&dyn Index<Winner>> = vec![&self.owed];
+    Box::new(indexes.into_iter())
+  }
+}
+
+/// Winners keyed by round and position, indexed by address and whether they're
+/// still owed a prize, so that what a player is owed in every round can be
+/// found without scanning every winner or every prize they've ever claimed.
+pub fn winners<'a>() -> IndexedMap<'a, (u64, u32), Winner, WinnerIndexes<'a>> {
+  IndexedMap::new(
+    "round_winners",
+    WinnerIndexes {
+      owed: MultiIndex::new(
+        |_, winner| (winner.address.clone(), winner.is_owed() as u8),
+        "round_winners",
+        "round_winners__owed",
+      ),
+    },
+  )
+}
+
+// the current round, with past rounds archived in ROUNDS when a new one starts
 pub const GAME: Item<Game> = Item::new("game");
-pub const ORDERS: Item<Vec<TicketOrder>> = Item::new("orders");
-pub const WINNERS: Map<u32, Winner> = Map::new("winners");
-pub const PLAYERS: Map<Addr, Player> = Map::new("players");
-pub const ADDR_2_INDEX: Map<Addr, u32> = Map::new("addr_2_index");
-pub const INDEX_2_ADDR: Map<u32, Addr> = Map::new("index_2_addr");
-pub const INDICES: Item<Vec<u32>> = Item::new("indices");
+pub const ROUNDS: Map<u64, Game> = Map::new("rounds");
 pub const PREV_HEIGHT: Item<u64> = Item::new("prev_height");
 
+// Everything below is namespaced by round.
+pub const ORDERS: Map<(u64, u64), TicketOrder> = Map::new("ticket_orders");
+// winners are stored in `winners()`, keyed by round and position
+// next winning position to pay in rounds whose prizes are distributed
+pub const DISTRIBUTION_CURSORS: Map<u64, u32> = Map::new("distribution_cursors");
+// players are stored in `players()`, keyed by round and address
+pub const COMMITMENTS: Map<(u64, Addr), Commitment> = Map::new("commitments");
+pub const DRAND_RANDOMNESS: Map<u64, Binary> = Map::new("drand_randomness");
+pub const SEED_FINALIZATION: Map<u64, SeedFinalization> = Map::new("seed_finalization");
+// seed of a round that was already underway when the contract was migrated
+pub const SEED_CHECKPOINTS: Map<u64, SeedCheckpoint> = Map::new("seed_checkpoints");
+// total paid for tickets in each asset, keyed by `Asset::key`
+pub const POTS: Map<(u64, String), Uint128> = Map::new("pots");
+// amount paid by each player in each asset, for refunds
+pub const PAYMENTS: Map<(u64, Addr, String), Uint128> = Map::new("payments");
+// jackpot seeded into each round in each asset, on top of its pot
+pub const JACKPOTS: Map<(u64, String), Uint128> = Map::new("jackpots");
+
+// Storage layout prior to keying ticket orders by sequence number. These are
+// only read when migrating an existing game.
+const LEGACY_ORDERS: Item<Vec<TicketOrder>> = Item::new("orders");
+const LEGACY_ADDR_2_INDEX: Map<Addr, u32> = Map::new("addr_2_index");
+const LEGACY_INDEX_2_ADDR: Map<u32, Addr> = Map::new("index_2_addr");
+const LEGACY_INDICES: Item<Vec<u32>> = Item::new("indices");
+// Storage layout prior to namespacing by round.
+const LEGACY_PLAYERS: Map<Addr, Player> = Map::new("players");
+const LEGACY_WINNERS: Map<u32, Winner> = Map::new("winners");
+// The game as stored prior to configurable royalties and recorded creation
+// heights, read only to tell which of them are missing.
+const LEGACY_GAME: Item<LegacyGame> = Item::new("game");
+
+#[derive(Serialize, Deserialize)]
+struct LegacyGame {
+  royalties: Option<Vec<Royalty>>,
+  created_at_height: Option<u64>,
+}
+
 /// Initialize contract state data.
 pub fn initialize(
   deps: DepsMut,
@@ -72,34 +278,1069 @@ pub fn initialize(
   info: &MessageInfo,
   msg: &InstantiateMsg,
 ) -> Result<(), ContractError> {
+  let owner = match &msg.owner {
+    Some(owner) => deps.api.addr_validate(owner.as_str())?,
+    None => info.sender.clone(),
+  };
+  let royalties = validate_royalties(deps.api, &msg.royalties)?;
+  let randomness = validate_randomness(deps.api, &owner, &msg.randomness)?;
+  let caller_policy = validate_caller_policy(deps.api, &msg.caller_policy)?;
+  let extra_prices = validate_extra_prices(deps.api, msg)?;
+  let jackpot = validate_jackpot(deps.api, &msg.jackpot)?;
+  let claim_deadline = validate_claim_deadline(deps.api, msg, &jackpot)?;
+  let registry = match &msg.registry {
+    Some(registry) => Some(deps.api.addr_validate(registry.as_str())?),
+    None => None,
+  };
+  validate_selection(&msg.selection)?;
   let game = Game {
     seed: random::seed::init(&msg.id, env.block.height),
+    created_at_height: env.block.height,
     name: msg.name.clone(),
-    owner: info.sender.clone(),
+    owner,
     status: GameStatus::ACTIVE,
     id: msg.id.clone(),
+    round: 0,
     selection: msg.selection.clone(),
     ticket_price: Uint128::try_from(&msg.ticket_price[..])?,
-    ends_after: match msg.duration_minutes {
-      Some(duration_minutes) => Some(env.block.time.plus_seconds(60 * duration_minutes as u64)),
-      None => None,
-    },
+    ends_after: msg
+      .duration_minutes
+      .map(|duration_minutes| env.block.time.plus_seconds(60 * duration_minutes as u64)),
+    duration_minutes: msg.duration_minutes,
+    auto_start_round: msg.auto_start_round,
+    auto_distribute: msg.auto_distribute,
     denom: msg.denom.clone(),
     cw20_token_address: msg.cw20_token_address.clone(),
-    max_tickets_per_player: msg.max_tickets_per_player.clone(),
+    extra_prices,
+    max_tickets_per_player: msg.max_tickets_per_player,
     has_distinct_winners: msg.has_distinct_winners,
-    funding_threshold: msg.funding_threshold.clone(),
+    funding_threshold: msg.funding_threshold,
     player_count: 0,
+    closed_at: None,
+    drand_round: None,
     ticket_count: 0,
+    order_count: 0,
     ended_at: None,
     ended_by: None,
+    canceled_at: None,
+    canceled_by: None,
+    royalties,
+    randomness,
+    caller_policy,
+    jackpot,
+    claim_deadline,
+    registry,
   };
 
   GAME.save(deps.storage, &game)?;
-  ORDERS.save(deps.storage, &vec![])?;
-  INDICES.save(deps.storage, &vec![])?;
+
+  // the owner may commit to their secret up front
+  if let Some(hash) = &msg.commitment {
+    if !game.is_committer(&game.owner) {
+      return Err(ContractError::NotCommitter {});
+    }
+    COMMITMENTS.save(
+      deps.storage,
+      (game.round, game.owner.clone()),
+      &Commitment {
+        hash: hash.clone(),
+        secret: None,
+      },
+    )?;
+  }
 
   Ok(())
 }
 
-impl Game {}
+/// Bring the storage of a game created by an earlier version of the contract
+/// up to date.
+pub fn migrate(
+  deps: DepsMut,
+  env: &Env,
+) -> Result<(), ContractError> {
+  // read before the game is saved back with the fields it was missing
+  let has_creation_height = LEGACY_GAME.load(deps.storage)?.created_at_height.is_some();
+  migrate_royalties(deps.storage)?;
+  // players' join order is read from the legacy index before it's dropped
+  migrate_rounds(deps.storage)?;
+  migrate_orders(deps.storage)?;
+  migrate_pots(deps.storage)?;
+  if !has_creation_height {
+    checkpoint_seed(deps.storage, env.block.height)?;
+  }
+  Ok(())
+}
+
+/// Record the seed of a game created before its creation height was stored,
+/// since its seed history can't be recomputed from `seed::init`.
+fn checkpoint_seed(
+  storage: &mut dyn Storage,
+  block_height: u64,
+) -> Result<(), ContractError> {
+  let game = GAME.load(storage)?;
+  let checkpoint = SeedCheckpoint {
+    seq: game.order_count,
+    block_height,
+    seed: game.seed,
+  };
+  SEED_CHECKPOINTS.save(storage, game.round, &checkpoint)?;
+  Ok(())
+}
+
+/// Give a game created before royalties were configurable the royalties it was
+/// created with, unless it's already over and they have been paid.
+fn migrate_royalties(storage: &mut dyn Storage) -> Result<(), ContractError> {
+  if LEGACY_GAME.load(storage)?.royalties.is_some() {
+    return Ok(());
+  }
+
+  let mut game = GAME.load(storage)?;
+  if !matches!(game.status, GameStatus::ENDED | GameStatus::CANCELED) {
+    game.royalties = LEGACY_ROYALTIES
+      .iter()
+      .map(|(address, basis_points, label)| Royalty {
+        address: Addr::unchecked(*address),
+        basis_points: *basis_points,
+        label: Some(label.to_string()),
+      })
+      .chain(std::iter::once(Royalty {
+        address: game.owner.clone(),
+        basis_points: LEGACY_OWNER_ROYALTY_BASIS_POINTS,
+        label: Some("admin".to_owned()),
+      }))
+      .collect();
+  }
+  GAME.save(storage, &game)?;
+  Ok(())
+}
+
+/// Move ticket orders from the legacy `Vec<TicketOrder>` item into the
+/// `ORDERS` map, dropping the per-ticket index vector along the way.
+fn migrate_orders(storage: &mut dyn Storage) -> Result<(), ContractError> {
+  let legacy_orders = match LEGACY_ORDERS.may_load(storage)? {
+    Some(legacy_orders) => legacy_orders,
+    None => return Ok(()),
+  };
+
+  let mut game = GAME.load(storage)?;
+  for (seq, order) in legacy_orders.iter().enumerate() {
+    ORDERS.save(storage, (0, seq as u64), order)?;
+  }
+  game.order_count = legacy_orders.len() as u64;
+  GAME.save(storage, &game)?;
+
+  let addrs: Vec<Addr> = LEGACY_ADDR_2_INDEX
+    .keys(storage, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<Addr>>>()?;
+  for addr in addrs.iter() {
+    let index = LEGACY_ADDR_2_INDEX.load(storage, addr.clone())?;
+    LEGACY_INDEX_2_ADDR.remove(storage, index);
+    LEGACY_ADDR_2_INDEX.remove(storage, addr.clone());
+  }
+
+  LEGACY_ORDERS.remove(storage);
+  LEGACY_INDICES.remove(storage);
+
+  Ok(())
+}
+
+/// Move players and winners into the first round. Players are ordered by when
+/// they joined according to the legacy 1-based player index.
+fn migrate_rounds(storage: &mut dyn Storage) -> Result<(), ContractError> {
+  let legacy_players: Vec<(Addr, Player)> = LEGACY_PLAYERS
+    .range(storage, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<(Addr, Player)>>>()?;
+  for (i, (addr, mut player)) in legacy_players.into_iter().enumerate() {
+    player.joined_at = match LEGACY_ADDR_2_INDEX.may_load(storage, addr.clone())? {
+      Some(index) => index.saturating_sub(1),
+      None => i as u32,
+    };
+    players().save(storage, (0, addr.clone()), &player)?;
+    LEGACY_PLAYERS.remove(storage, addr);
+  }
+
+  let legacy_winners: Vec<(u32, Winner)> = LEGACY_WINNERS
+    .range(storage, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<(u32, Winner)>>>()?;
+  for (position, winner) in legacy_winners.into_iter() {
+    winners().save(storage, (0, position), &winner)?;
+    LEGACY_WINNERS.remove(storage, position);
+  }
+
+  Ok(())
+}
+
+/// Record the pot and each player's payments for a game whose tickets were all
+/// bought with its primary asset, before pots were tracked per asset.
+fn migrate_pots(storage: &mut dyn Storage) -> Result<(), ContractError> {
+  let game = GAME.load(storage)?;
+  let key = game.primary_asset().key();
+  if game.ticket_count == 0 || POTS.has(storage, (game.round, key.clone())) {
+    return Ok(());
+  }
+
+  POTS.save(
+    storage,
+    (game.round, key.clone()),
+    &(game.ticket_price * Uint128::from(game.ticket_count)),
+  )?;
+
+  let round_players: Vec<(Addr, Player)> = players()
+    .prefix(game.round)
+    .range(storage, None, None, Order::Ascending)
+    .collect::<StdResult<Vec<(Addr, Player)>>>()?;
+  for (addr, player) in round_players.into_iter() {
+    PAYMENTS.save(
+      storage,
+      (game.round, addr, key.clone()),
+      &(game.ticket_price * Uint128::from(player.ticket_count)),
+    )?;
+  }
+
+  Ok(())
+}
+
+/// Load the pot of each asset in the game's price table, in the same order.
+pub fn load_pots(
+  storage: &dyn Storage,
+  game: &Game,
+) -> StdResult<Vec<AssetAmount>> {
+  game
+    .price_table()
+    .into_iter()
+    .map(|price| {
+      let amount = POTS
+        .may_load(storage, (game.round, price.asset.key()))?
+        .unwrap_or_default();
+      Ok(AssetAmount {
+        asset: price.asset,
+        amount,
+      })
+    })
+    .collect()
+}
+
+/// Load the jackpot seeded into the game's round in each asset of its price
+/// table, in the same order.
+pub fn load_jackpot(
+  storage: &dyn Storage,
+  game: &Game,
+) -> StdResult<Vec<AssetAmount>> {
+  game
+    .price_table()
+    .into_iter()
+    .map(|price| {
+      let amount = JACKPOTS
+        .may_load(storage, (game.round, price.asset.key()))?
+        .unwrap_or_default();
+      Ok(AssetAmount {
+        asset: price.asset,
+        amount,
+      })
+    })
+    .collect()
+}
+
+/// Load the current round or, if given, a past round.
+pub fn load_round(
+  storage: &dyn Storage,
+  round: Option<u64>,
+) -> StdResult<Game> {
+  let game = GAME.load(storage)?;
+  match round {
+    Some(round) if round != game.round => ROUNDS.load(storage, round),
+    _ => Ok(game),
+  }
+}
+
+/// Positions at which an address is still owed a prize in every round or only
+/// in the given one, along with the winners at those positions, ordered by
+/// round and position.
+pub fn find_winnings(
+  storage: &dyn Storage,
+  addr: &Addr,
+  round: Option<u64>,
+) -> StdResult<Vec<((u64, u32), Winner)>> {
+  let (min, max) = match round {
+    Some(round) => (
+      Some(Bound::inclusive((round, 0))),
+      Some(Bound::inclusive((round, u32::MAX))),
+    ),
+    None => (None, None),
+  };
+  winners()
+    .idx
+    .owed
+    .prefix((addr.clone(), 1))
+    .range(storage, min, max, Order::Ascending)
+    .collect()
+}
+
+/// Find the order containing the ticket at the given 0-based index by binary
+/// searching over each order's cumulative ticket count in the given round.
+pub fn find_ticket_order(
+  storage: &dyn Storage,
+  round: u64,
+  order_count: u64,
+  ticket_index: u64,
+) -> StdResult<TicketOrder> {
+  let mut lo = 0u64;
+  let mut hi = order_count;
+  while lo < hi {
+    let mid = lo + (hi - lo) / 2;
+    if ORDERS.load(storage, (round, mid))?.cum_count <= ticket_index {
+      lo = mid + 1;
+    } else {
+      hi = mid;
+    }
+  }
+  match ORDERS.may_load(storage, (round, lo))? {
+    Some(order) => Ok(order),
+    None => Err(StdError::not_found("TicketOrder")),
+  }
+}
+
+/// Validate royalty recipient addresses and ensure that the total taken out of
+/// the jackpot doesn't exceed the global max.
+fn validate_royalties(
+  api: &dyn Api,
+  royalties: &[RoyaltyMsg],
+) -> Result<Vec<Royalty>, ContractError> {
+  let mut total_basis_points: u32 = 0;
+  let mut validated: Vec<Royalty> = Vec::with_capacity(royalties.len());
+  for royalty in royalties.iter() {
+    total_basis_points += royalty.basis_points as u32;
+    validated.push(Royalty {
+      address: api.addr_validate(&royalty.address)?,
+      basis_points: royalty.basis_points,
+      label: royalty.label.clone(),
+    });
+  }
+  if total_basis_points > MAX_TOTAL_ROYALTY_BASIS_POINTS as u32 {
+    return Err(ContractError::ExcessRoyalties {
+      max_basis_points: MAX_TOTAL_ROYALTY_BASIS_POINTS,
+    });
+  }
+  Ok(validated)
+}
+
+/// Validate the addresses of CW20 tokens tickets may also be bought with,
+/// ensuring that no asset is priced twice.
+fn validate_extra_prices(
+  api: &dyn Api,
+  msg: &InstantiateMsg,
+) -> Result<Vec<AssetAmount>, ContractError> {
+  let mut keys: Vec<String> = vec![match &msg.cw20_token_address {
+    Some(address) => Asset::Cw20 {
+      address: address.clone(),
+    },
+    None => Asset::Native {
+      denom: msg.denom.clone(),
+    },
+  }
+  .key()];
+  let mut validated: Vec<AssetAmount> = Vec::with_capacity(msg.extra_prices.len());
+  for price in msg.extra_prices.iter() {
+    let asset = match &price.asset {
+      Asset::Cw20 { address } => Asset::Cw20 {
+        address: api.addr_validate(address.as_str())?,
+      },
+      asset => asset.clone(),
+    };
+    if keys.contains(&asset.key()) {
+      return Err(ContractError::DuplicateAsset {
+        asset: asset.to_string(),
+      });
+    }
+    keys.push(asset.key());
+    validated.push(AssetAmount {
+      asset,
+      amount: price.amount,
+    });
+  }
+  Ok(validated)
+}
+
+/// Validate committer addresses, defaulting to the owner as the sole committer
+/// if none are given, the drand network's parameters, or the randomness
+/// proxy's address.
+fn validate_randomness(
+  api: &dyn Api,
+  owner: &Addr,
+  randomness: &Option<RandomnessSource>,
+) -> Result<RandomnessSource, ContractError> {
+  match randomness {
+    None | Some(RandomnessSource::Seed {}) => Ok(RandomnessSource::Seed {}),
+    Some(RandomnessSource::CommitReveal {
+      committers,
+      reveal_timeout_minutes,
+    }) => {
+      let mut validated: Vec<Addr> = Vec::with_capacity(std::cmp::max(1, committers.len()));
+      for committer in committers.iter() {
+        validated.push(api.addr_validate(committer.as_str())?);
+      }
+      if validated.is_empty() {
+        validated.push(owner.clone());
+      }
+      Ok(RandomnessSource::CommitReveal {
+        committers: validated,
+        reveal_timeout_minutes: *reveal_timeout_minutes,
+      })
+    },
+    Some(RandomnessSource::Proxy {
+      address,
+      callback_timeout_minutes,
+    }) => Ok(RandomnessSource::Proxy {
+      address: api.addr_validate(address.as_str())?,
+      callback_timeout_minutes: *callback_timeout_minutes,
+    }),
+    Some(
+      source @ RandomnessSource::Drand {
+        pubkey, period_seconds, ..
+      },
+    ) => {
+      drand::parse_pubkey(pubkey.as_slice())?;
+      if *period_seconds == 0 {
+        return Err(ContractError::InvalidDrandConfig {});
+      }
+      Ok(source.clone())
+    },
+  }
+}
+
+/// Validate the addresses of allowlisted contracts, defaulting to allowing
+/// only externally owned accounts.
+fn validate_caller_policy(
+  api: &dyn Api,
+  caller_policy: &Option<CallerPolicy>,
+) -> Result<CallerPolicy, ContractError> {
+  match caller_policy {
+    None => Ok(CallerPolicy::default()),
+    Some(CallerPolicy::Allowlist { contracts }) => Ok(CallerPolicy::Allowlist {
+      contracts: contracts
+        .iter()
+        .map(|contract| api.addr_validate(contract.as_str()))
+        .collect::<StdResult<Vec<Addr>>>()?,
+    }),
+    Some(policy) => Ok(policy.clone()),
+  }
+}
+
+/// Ensure that winners can be selected as configured and that their prizes are
+/// allocated without paying out more than the winnings.
+fn validate_selection(selection: &WinnerSelection) -> Result<(), ContractError> {
+  match selection {
+    WinnerSelection::Fixed {
+      pct_split,
+      winner_count,
+      max_winner_count,
+    } => {
+      // a cap below the winner count would leave part of the split unpaid
+      if let Some(max_winner_count) = max_winner_count {
+        if *max_winner_count > 0 && *max_winner_count < *winner_count {
+          return Err(ContractError::InvalidMaxWinnerCount {
+            max_winner_count: *max_winner_count,
+            winner_count: *winner_count,
+          });
+        }
+      }
+      validate_pct_split(pct_split, *winner_count)
+    },
+    WinnerSelection::Percent { pct_player_count } => {
+      if *pct_player_count == 0 || *pct_player_count > 100 {
+        return Err(ContractError::InvalidPctPlayerCount {
+          pct_player_count: *pct_player_count,
+        });
+      }
+      Ok(())
+    },
+    WinnerSelection::Lotto {
+      numbers_per_ticket,
+      number_range,
+      prize_tiers,
+    } => validate_lotto(*numbers_per_ticket, *number_range, prize_tiers),
+  }
+}
+
+/// Every winner must be entitled to a share of the winnings, and the shares
+/// must add up to all of it.
+fn validate_pct_split(
+  pct_split: &[u8],
+  winner_count: u32,
+) -> Result<(), ContractError> {
+  if winner_count == 0 {
+    return Err(ContractError::InvalidWinnerCount {});
+  }
+  if pct_split.len() != winner_count as usize {
+    return Err(ContractError::PctSplitLengthMismatch {
+      winner_count,
+      split_count: pct_split.len() as u32,
+    });
+  }
+  if let Some(position) = pct_split.iter().position(|pct| *pct == 0) {
+    return Err(ContractError::ZeroPctSplit {
+      position: position as u32,
+    });
+  }
+  let total: u32 = pct_split.iter().map(|pct| *pct as u32).sum();
+  if total != 100 {
+    return Err(ContractError::PctSplitTotalNot100 { total });
+  }
+  Ok(())
+}
+
+/// Tickets must pick at least one number in range, and each prize tier must
+/// match a distinct number of them, with the tiers splitting at most all of
+/// the winnings.
+fn validate_lotto(
+  numbers_per_ticket: u8,
+  number_range: u8,
+  prize_tiers: &[PrizeTier],
+) -> Result<(), ContractError> {
+  if numbers_per_ticket == 0 || number_range < numbers_per_ticket {
+    return Err(ContractError::InvalidNumbersPerTicket {
+      numbers_per_ticket,
+      number_range,
+    });
+  }
+  if prize_tiers.is_empty() {
+    return Err(ContractError::NoPrizeTiers {});
+  }
+  let total: u32 = prize_tiers.iter().map(|tier| tier.pct as u32).sum();
+  if total > 100 {
+    return Err(ContractError::PrizeTierTotalExceeds100 { total });
+  }
+  for (i, tier) in prize_tiers.iter().enumerate() {
+    if tier.matches > numbers_per_ticket {
+      return Err(ContractError::InvalidPrizeTierMatches { matches: tier.matches });
+    }
+    if prize_tiers[..i].iter().any(|other| other.matches == tier.matches) {
+      return Err(ContractError::DuplicatePrizeTier { matches: tier.matches });
+    }
+  }
+  Ok(())
+}
+
+/// Validate the jackpot's odds and the address to which it's carried over.
+fn validate_jackpot(
+  api: &dyn Api,
+  jackpot: &Option<Jackpot>,
+) -> Result<Option<Jackpot>, ContractError> {
+  let jackpot = match jackpot {
+    Some(jackpot) => jackpot,
+    None => return Ok(None),
+  };
+  if jackpot.hit_basis_points as u128 > BASIS_POINTS_DENOMINATOR {
+    return Err(ContractError::InvalidJackpot {});
+  }
+  Ok(Some(Jackpot {
+    hit_basis_points: jackpot.hit_basis_points,
+    carry_over: validate_carry_over(api, &jackpot.carry_over)?,
+  }))
+}
+
+/// Validate the claim deadline and where unclaimed prizes are swept to. Funds
+/// swept into the next round are added to its jackpot, so that round must pay
+/// out its jackpot: either the game has one or it's a lotto game.
+fn validate_claim_deadline(
+  api: &dyn Api,
+  msg: &InstantiateMsg,
+  jackpot: &Option<Jackpot>,
+) -> Result<Option<ClaimDeadline>, ContractError> {
+  let claim_deadline = match &msg.claim_deadline {
+    Some(claim_deadline) => claim_deadline,
+    None => return Ok(None),
+  };
+  if claim_deadline.minutes == 0 {
+    return Err(ContractError::InvalidClaimDeadline {});
+  }
+  if claim_deadline.sweep_to == (CarryOver::NextRound {})
+    && jackpot.is_none()
+    && !matches!(msg.selection, WinnerSelection::Lotto { .. })
+  {
+    return Err(ContractError::InvalidClaimDeadline {});
+  }
+  Ok(Some(ClaimDeadline {
+    minutes: claim_deadline.minutes,
+    sweep_to: validate_carry_over(api, &claim_deadline.sweep_to)?,
+  }))
+}
+
+/// Validate the address to which funds are carried over, if any.
+fn validate_carry_over(
+  api: &dyn Api,
+  carry_over: &CarryOver,
+) -> StdResult<CarryOver> {
+  Ok(match carry_over {
+    CarryOver::NextRound {} => CarryOver::NextRound {},
+    CarryOver::Lottery { address } => CarryOver::Lottery {
+      address: api.addr_validate(address.as_str())?,
+    },
+    CarryOver::Address { address } => CarryOver::Address {
+      address: api.addr_validate(address.as_str())?,
+    },
+  })
+}
+
+impl Game {
+  /// Does the draw depend on entropy submitted after ticket sales close?
+  pub fn requires_entropy(&self) -> bool {
+    !matches!(self.randomness, RandomnessSource::Seed {})
+  }
+
+  pub fn is_committer(
+    &self,
+    addr: &Addr,
+  ) -> bool {
+    match &self.randomness {
+      RandomnessSource::CommitReveal { committers, .. } => committers.contains(addr),
+      _ => false,
+    }
+  }
+
+  /// Time after which a closed game that is still waiting on entropy may be
+  /// canceled by anyone.
+  pub fn entropy_deadline(&self) -> Option<Timestamp> {
+    match (&self.randomness, self.closed_at, self.drand_round) {
+      (
+        RandomnessSource::CommitReveal {
+          reveal_timeout_minutes, ..
+        },
+        Some(closed_at),
+        _,
+      ) => Some(closed_at.plus_seconds(60 * *reveal_timeout_minutes as u64)),
+      (
+        RandomnessSource::Drand {
+          genesis_time,
+          period_seconds,
+          beacon_timeout_minutes,
+          ..
+        },
+        _,
+        Some(round),
+      ) => {
+        Some(drand::round_time(*genesis_time, *period_seconds, round).plus_seconds(60 * *beacon_timeout_minutes as u64))
+      },
+      (
+        RandomnessSource::Proxy {
+          callback_timeout_minutes,
+          ..
+        },
+        Some(closed_at),
+        _,
+      ) => Some(closed_at.plus_seconds(60 * *callback_timeout_minutes as u64)),
+      _ => None,
+    }
+  }
+
+  /// Time after which prizes left unclaimed in an ended round may be swept.
+  pub fn claim_deadline_at(&self) -> Option<Timestamp> {
+    match (&self.claim_deadline, self.ended_at) {
+      (Some(claim_deadline), Some(ended_at)) => Some(ended_at.plus_seconds(60 * claim_deadline.minutes as u64)),
+      _ => None,
+    }
+  }
+
+  /// Asset in which `ticket_price` is denominated.
+  pub fn primary_asset(&self) -> Asset {
+    match &self.cw20_token_address {
+      Some(address) => Asset::Cw20 {
+        address: address.clone(),
+      },
+      None => Asset::Native {
+        denom: self.denom.clone(),
+      },
+    }
+  }
+
+  /// Price per ticket in every accepted asset, starting with the primary one.
+  pub fn price_table(&self) -> Vec<AssetAmount> {
+    let mut prices = Vec::with_capacity(1 + self.extra_prices.len());
+    prices.push(AssetAmount {
+      asset: self.primary_asset(),
+      amount: self.ticket_price,
+    });
+    prices.extend(self.extra_prices.iter().cloned());
+    prices
+  }
+
+  /// Price per ticket in the given asset, if tickets can be bought with it.
+  pub fn price_of(
+    &self,
+    asset: &Asset,
+  ) -> Option<Uint128> {
+    self
+      .price_table()
+      .into_iter()
+      .find(|price| price.asset == *asset)
+      .map(|price| price.amount)
+  }
+
+  /// Job ID under which randomness for the current round is requested from a
+  /// randomness proxy.
+  pub fn job_id(&self) -> String {
+    format!("{}/{}", self.id, self.round)
+  }
+
+  /// Sum of all royalties taken out of the jackpot when the game ends.
+  pub fn royalty_basis_points(&self) -> u16 {
+    self.royalties.iter().map(|royalty| royalty.basis_points).sum()
+  }
+}
+
+impl Winner {
+  /// Everything owed to the winner, starting with the primary asset.
+  pub fn claims(
+    &self,
+    game: &Game,
+  ) -> Vec<AssetAmount> {
+    let mut claims = Vec::with_capacity(1 + self.extra_claim_amounts.len());
+    claims.push(AssetAmount {
+      asset: game.primary_asset(),
+      amount: self.claim_amount,
+    });
+    claims.extend(self.extra_claim_amounts.iter().cloned());
+    claims
+  }
+
+  /// Whether the winner has yet to claim their prize, or to receive a prize
+  /// whose distribution failed.
+  pub fn is_owed(&self) -> bool {
+    !self.has_claimed || !self.undelivered.is_empty()
+  }
+
+  /// Everything the winner can still claim: their prize if they haven't
+  /// claimed it, otherwise any prize whose distribution failed.
+  pub fn claimable(
+    &self,
+    game: &Game,
+  ) -> Vec<AssetAmount> {
+    let claimable = if self.has_claimed {
+      self.undelivered.clone()
+    } else {
+      self.claims(game)
+    };
+    claimable.into_iter().filter(|claim| !claim.amount.is_zero()).collect()
+  }
+
+  /// Replace everything owed to the winner, given amounts of each asset
+  /// starting with the primary one.
+  pub fn set_claims(
+    &mut self,
+    claims: Vec<AssetAmount>,
+  ) {
+    let mut claims = claims.into_iter();
+    self.claim_amount = claims.next().map(|claim| claim.amount).unwrap_or_default();
+    self.extra_claim_amounts = claims.filter(|claim| !claim.amount.is_zero()).collect();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::contract;
+  use crate::msg::MigrateMsg;
+  use crate::query;
+  use cosmwasm_std::testing::{mock_dependencies, mock_env};
+  use cosmwasm_std::to_vec;
+
+  fn fixed(
+    pct_split: Vec<u8>,
+    winner_count: u32,
+    max_winner_count: Option<u32>,
+  ) -> WinnerSelection {
+    WinnerSelection::Fixed {
+      pct_split,
+      winner_count,
+      max_winner_count,
+    }
+  }
+
+  fn lotto(
+    numbers_per_ticket: u8,
+    number_range: u8,
+    prize_tiers: &[(u8, u8)],
+  ) -> WinnerSelection {
+    WinnerSelection::Lotto {
+      numbers_per_ticket,
+      number_range,
+      prize_tiers: prize_tiers
+        .iter()
+        .map(|(matches, pct)| PrizeTier {
+          matches: *matches,
+          pct: *pct,
+        })
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn accepts_valid_selections() {
+    let selections = vec![
+      fixed(vec![100], 1, None),
+      fixed(vec![60, 30, 10], 3, Some(0)),
+      fixed(vec![60, 30, 10], 3, Some(3)),
+      fixed(vec![50, 50], 2, Some(5)),
+      WinnerSelection::Percent { pct_player_count: 1 },
+      WinnerSelection::Percent { pct_player_count: 100 },
+      lotto(1, 1, &[(1, 100)]),
+      lotto(6, 49, &[(6, 50), (5, 30), (4, 10), (0, 10)]),
+      lotto(3, 10, &[(3, 40)]),
+    ];
+    for selection in selections.iter() {
+      assert!(validate_selection(selection).is_ok(), "{:?}", selection);
+    }
+  }
+
+  #[test]
+  fn rejects_invalid_selections() {
+    let cases = vec![
+      (fixed(vec![], 0, None), ContractError::InvalidWinnerCount {}),
+      (
+        fixed(vec![60, 30, 10], 3, Some(2)),
+        ContractError::InvalidMaxWinnerCount {
+          max_winner_count: 2,
+          winner_count: 3,
+        },
+      ),
+      (
+        fixed(vec![50, 50], 3, None),
+        ContractError::PctSplitLengthMismatch {
+          winner_count: 3,
+          split_count: 2,
+        },
+      ),
+      (
+        fixed(vec![60, 30], 2, None),
+        ContractError::PctSplitTotalNot100 { total: 90 },
+      ),
+      (
+        fixed(vec![100, 0], 2, None),
+        ContractError::ZeroPctSplit { position: 1 },
+      ),
+      (
+        WinnerSelection::Percent { pct_player_count: 0 },
+        ContractError::InvalidPctPlayerCount { pct_player_count: 0 },
+      ),
+      (
+        WinnerSelection::Percent { pct_player_count: 101 },
+        ContractError::InvalidPctPlayerCount { pct_player_count: 101 },
+      ),
+      (
+        lotto(0, 10, &[(0, 100)]),
+        ContractError::InvalidNumbersPerTicket {
+          numbers_per_ticket: 0,
+          number_range: 10,
+        },
+      ),
+      (
+        lotto(6, 5, &[(6, 100)]),
+        ContractError::InvalidNumbersPerTicket {
+          numbers_per_ticket: 6,
+          number_range: 5,
+        },
+      ),
+      (lotto(3, 10, &[]), ContractError::NoPrizeTiers {}),
+      (
+        lotto(3, 10, &[(3, 60), (2, 50)]),
+        ContractError::PrizeTierTotalExceeds100 { total: 110 },
+      ),
+      (
+        lotto(3, 10, &[(4, 100)]),
+        ContractError::InvalidPrizeTierMatches { matches: 4 },
+      ),
+      (
+        lotto(3, 10, &[(3, 50), (3, 50)]),
+        ContractError::DuplicatePrizeTier { matches: 3 },
+      ),
+    ];
+    for (selection, error) in cases.into_iter() {
+      // errors don't implement `PartialEq`, so compare their fields by debug output
+      let result = validate_selection(&selection).unwrap_err();
+      assert_eq!(format!("{:?}", result), format!("{:?}", error), "{:?}", selection);
+    }
+  }
+
+  #[test]
+  fn finds_only_owed_winnings() {
+    let mut deps = mock_dependencies();
+    let player = Addr::unchecked("player");
+    let winner = |has_claimed: bool, undelivered: Vec<AssetAmount>| Winner {
+      address: player.clone(),
+      position: 0,
+      ticket_count: 1,
+      has_claimed,
+      claim_amount: Uint128::new(100),
+      extra_claim_amounts: vec![],
+      matches: None,
+      undelivered,
+      is_swept: false,
+    };
+    let undelivered = vec![AssetAmount {
+      asset: Asset::Native {
+        denom: "ujuno".to_owned(),
+      },
+      amount: Uint128::new(100),
+    }];
+    let storage = deps.as_mut().storage;
+    winners().save(storage, (0, 0), &winner(false, vec![])).unwrap();
+    winners().save(storage, (0, 1), &winner(true, vec![])).unwrap();
+    winners().save(storage, (1, 0), &winner(true, undelivered)).unwrap();
+    winners().save(storage, (2, 0), &winner(false, vec![])).unwrap();
+
+    let positions = |storage: &dyn Storage, round: Option<u64>| -> Vec<(u64, u32)> {
+      find_winnings(storage, &player, round)
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect()
+    };
+    assert_eq!(positions(&deps.storage, None), vec![(0, 0), (1, 0), (2, 0)]);
+    assert_eq!(positions(&deps.storage, Some(1)), vec![(1, 0)]);
+
+    // claiming drops the winner from the index
+    winners()
+      .save(&mut deps.storage, (2, 0), &winner(true, vec![]))
+      .unwrap();
+    assert_eq!(positions(&deps.storage, None), vec![(0, 0), (1, 0)]);
+  }
+
+  // a finished game as serialized by the original version of the contract
+  const BASELINE_GAME: &str = r#"{
+    "owner": "owner",
+    "name": "Baseline",
+    "id": "baseline",
+    "status": "e_n_d_e_d",
+    "selection": {"fixed": {"pct_split": [100], "winner_count": 1, "max_winner_count": null}},
+    "player_count": 2,
+    "ended_at": "1571797419879305533",
+    "ended_by": "player_a",
+    "denom": "ujuno",
+    "cw20_token_address": null,
+    "ticket_price": "100",
+    "ticket_count": 3,
+    "seed": "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+    "ends_after": null,
+    "has_distinct_winners": true,
+    "max_tickets_per_player": null,
+    "funding_threshold": null
+  }"#;
+
+  const BASELINE_ORDERS: &str = r#"[
+    {"owner": "player_b", "count": 1, "cum_count": 1},
+    {"owner": "player_a", "count": 1, "cum_count": 2},
+    {"owner": "player_b", "count": 1, "cum_count": 3}
+  ]"#;
+
+  #[test]
+  fn migrates_baseline_game() {
+    let mut deps = mock_dependencies();
+    let player_a = Addr::unchecked("player_a");
+    let player_b = Addr::unchecked("player_b");
+
+    let storage = deps.as_mut().storage;
+    storage.set(b"game", BASELINE_GAME.as_bytes());
+    storage.set(b"orders", BASELINE_ORDERS.as_bytes());
+    storage.set(b"indices", &to_vec(&vec![1u32, 2, 1]).unwrap());
+    storage.set(&LEGACY_PLAYERS.key(player_a.clone()), br#"{"ticket_count": 1}"#);
+    storage.set(&LEGACY_PLAYERS.key(player_b.clone()), br#"{"ticket_count": 2}"#);
+    storage.set(&LEGACY_ADDR_2_INDEX.key(player_b.clone()), b"1");
+    storage.set(&LEGACY_ADDR_2_INDEX.key(player_a.clone()), b"2");
+    storage.set(&LEGACY_INDEX_2_ADDR.key(1), br#""player_b""#);
+    storage.set(&LEGACY_INDEX_2_ADDR.key(2), br#""player_a""#);
+    storage.set(
+      &LEGACY_WINNERS.key(0),
+      br#"{"address": "player_b", "position": 0, "ticket_count": 2, "has_claimed": false, "claim_amount": "270"}"#,
+    );
+
+    contract::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let game = GAME.load(&deps.storage).unwrap();
+    assert_eq!(game.round, 0);
+    assert_eq!(game.status, GameStatus::ENDED);
+    assert_eq!(game.order_count, 3);
+    assert_eq!(game.royalties, vec![]);
+    assert_eq!(game.randomness, RandomnessSource::Seed {});
+    assert_eq!(game.caller_policy, CallerPolicy::EoaOnly {});
+
+    let orders = ORDERS
+      .prefix(0)
+      .range(&deps.storage, None, None, Order::Ascending)
+      .map(|result| result.map(|(_, order)| (order.owner, order.cum_count)))
+      .collect::<StdResult<Vec<_>>>()
+      .unwrap();
+    assert_eq!(
+      orders,
+      vec![(player_b.clone(), 1), (player_a.clone(), 2), (player_b.clone(), 3)]
+    );
+    assert_eq!(find_ticket_order(&deps.storage, 0, 3, 1).unwrap().owner, player_a);
+
+    // player_b joined first, despite sorting after player_a
+    let migrated_a = players().load(&deps.storage, (0, player_a.clone())).unwrap();
+    let migrated_b = players().load(&deps.storage, (0, player_b.clone())).unwrap();
+    assert_eq!((migrated_a.ticket_count, migrated_a.joined_at), (1, 1));
+    assert_eq!((migrated_b.ticket_count, migrated_b.joined_at), (2, 0));
+    let winner = winners().load(&deps.storage, (0, 0)).unwrap();
+    assert_eq!(winner.address, player_b);
+    assert_eq!(winner.claim_amount, Uint128::new(270));
+    assert_eq!(find_winnings(&deps.storage, &player_b, None).unwrap().len(), 1);
+
+    let key = game.primary_asset().key();
+    assert_eq!(POTS.load(&deps.storage, (0, key.clone())).unwrap(), Uint128::new(300));
+    assert_eq!(
+      PAYMENTS.load(&deps.storage, (0, player_b.clone(), key)).unwrap(),
+      Uint128::new(200)
+    );
+
+    // the seed history starts from the seed found at migration
+    let history = query::get_seed_history(deps.as_ref(), None, None, None).unwrap();
+    assert_eq!(history.initial_seed, "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=");
+    assert_eq!(history.initial_block_height, mock_env().block.height);
+    assert_eq!(history.verifiable_from, 3);
+
+    // nothing is left behind in the legacy layout
+    for namespace in [&b"orders"[..], b"indices"] {
+      assert!(deps.storage.get(namespace).is_none());
+    }
+    assert!(LEGACY_PLAYERS.is_empty(&deps.storage));
+    assert!(LEGACY_WINNERS.is_empty(&deps.storage));
+    assert!(LEGACY_ADDR_2_INDEX.is_empty(&deps.storage));
+    assert!(LEGACY_INDEX_2_ADDR.is_empty(&deps.storage));
+  }
+
+  #[test]
+  fn migrates_royalties_of_running_baseline_game() {
+    let mut deps = mock_dependencies();
+    let game = BASELINE_GAME.replace("e_n_d_e_d", "a_c_t_i_v_e");
+    deps.storage.set(b"game", game.as_bytes());
+
+    contract::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let game = GAME.load(&deps.storage).unwrap();
+    let royalties: Vec<(&str, u16, Option<&str>)> = game
+      .royalties
+      .iter()
+      .map(|royalty| (royalty.address.as_str(), royalty.basis_points, royalty.label.as_deref()))
+      .collect();
+    assert_eq!(
+      royalties,
+      vec![
+        ("juno1jume25ttjlcaqqjzjjqx9humvze3vcc8z87szj", 250, Some("gelotto")),
+        ("juno1fxu5as8z5qxdulujzph3rm6c39r8427mjnx99r", 350, Some("annual_prize")),
+        ("juno18fd2xax0uh9dxusg8uae5rkeu8a4sv3gk6zm7h", 100, Some("nft_series_1")),
+        ("juno13c97054tjktvzvgqe2xfxj28j6wmhhlz03ut32", 100, Some("nft_series_2")),
+        (
+          "juno1dunhw3y4m6lu642lk20hfq9q3scr70l2vuyrwj",
+          100,
+          Some("owner_rewards")
+        ),
+        ("owner", 100, Some("admin")),
+      ]
+    );
+    assert_eq!(game.royalty_basis_points(), 1_000);
+
+    // migrating again leaves them as they are
+    contract::migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    assert_eq!(GAME.load(&deps.storage).unwrap().royalties, game.royalties);
+  }
+}
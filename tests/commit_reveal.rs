@@ -0,0 +1,71 @@
+mod common;
+
+use common::{assert_error, game_msg, Suite};
+use cosmwasm_std::Addr;
+use cw_lottery::error::ContractError;
+use cw_lottery::msg::{ExecuteMsg, GetGameResponse, QueryMsg, RandomnessSource};
+use cw_lottery::random::seed::digest;
+use cw_lottery::state::GameStatus;
+
+const SECRET: &str = "owner's secret";
+
+/// A game whose only committer is its owner, with tickets sold to two players.
+fn suite() -> Suite {
+  let mut msg = game_msg();
+  msg.randomness = Some(RandomnessSource::CommitReveal {
+    committers: vec![Addr::unchecked("owner")],
+    reveal_timeout_minutes: 60,
+  });
+  msg.commitment = Some(digest(SECRET));
+  let mut suite = Suite::new(msg);
+  for i in 0..2 {
+    let player = suite.player(i);
+    suite.buy(&player, 1);
+  }
+  suite
+}
+
+fn status(suite: &Suite) -> GameStatus {
+  suite
+    .query::<GetGameResponse>(&QueryMsg::GetGame { round: None })
+    .game
+    .status
+}
+
+#[test]
+fn committer_cannot_close_with_lucky_phrase() {
+  let mut suite = suite();
+  let owner = suite.owner.clone();
+
+  let close = ExecuteMsg::EndGame {
+    lucky_phrase: Some("lucky".to_owned()),
+  };
+  assert_error(
+    suite.execute(&owner, &close, &[]),
+    ContractError::CommitterLuckyPhrase {},
+  );
+  assert_eq!(status(&suite), GameStatus::ACTIVE);
+
+  suite.end_game().unwrap();
+  assert_eq!(status(&suite), GameStatus::CLOSED);
+}
+
+#[test]
+fn player_may_close_with_lucky_phrase() {
+  let mut suite = suite();
+  let alice = suite.player(0);
+  let owner = suite.owner.clone();
+
+  let close = ExecuteMsg::EndGame {
+    lucky_phrase: Some("lucky".to_owned()),
+  };
+  suite.execute(&alice, &close, &[]).unwrap();
+  assert_eq!(status(&suite), GameStatus::CLOSED);
+
+  let reveal = ExecuteMsg::Reveal {
+    secret: SECRET.to_owned(),
+  };
+  suite.execute(&owner, &reveal, &[]).unwrap();
+  suite.end_game().unwrap();
+  assert_eq!(status(&suite), GameStatus::ENDED);
+}
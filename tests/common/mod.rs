@@ -0,0 +1,273 @@
+//! Helpers shared by the multi-test suites. Each suite only uses some of them.
+#![allow(dead_code)]
+
+use cosmwasm_std::{coins, to_binary, Addr, Coin, Empty, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+use cw_lottery::asset::{Asset, AssetAmount};
+use cw_lottery::contract::{execute, instantiate, query, reply};
+use cw_lottery::error::ContractError;
+use cw_lottery::msg::{ExecuteMsg, InstantiateMsg, QueryMsg, ReceiveMsg, WinnerSelection};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use serde::de::DeserializeOwned;
+
+pub const DENOM: &str = "ujuno";
+pub const TICKET_PRICE: u128 = 100;
+pub const TOKEN_TICKET_PRICE: u128 = 50;
+pub const INITIAL_BALANCE: u128 = 10_000;
+
+pub fn lottery() -> Box<dyn Contract<Empty>> {
+  Box::new(ContractWrapper::new(execute, instantiate, query).with_reply(reply))
+}
+
+pub fn cw20_token() -> Box<dyn Contract<Empty>> {
+  Box::new(ContractWrapper::new(
+    cw20_base::contract::execute,
+    cw20_base::contract::instantiate,
+    cw20_base::contract::query,
+  ))
+}
+
+/// The price of a ticket in the suite's CW20 token, to add to `extra_prices`.
+pub fn token_price(token: &Addr) -> AssetAmount {
+  AssetAmount {
+    asset: Asset::Cw20 { address: token.clone() },
+    amount: Uint128::new(TOKEN_TICKET_PRICE),
+  }
+}
+
+/// A single-winner game with tickets priced in `DENOM`, to be adjusted by each
+/// test.
+pub fn game_msg() -> InstantiateMsg {
+  InstantiateMsg {
+    id: "game".to_owned(),
+    owner: None,
+    name: None,
+    duration_minutes: None,
+    denom: DENOM.to_owned(),
+    cw20_token_address: None,
+    ticket_price: TICKET_PRICE.to_string(),
+    extra_prices: vec![],
+    selection: WinnerSelection::Fixed {
+      pct_split: vec![100],
+      winner_count: 1,
+      max_winner_count: None,
+    },
+    has_distinct_winners: true,
+    max_tickets_per_player: None,
+    funding_threshold: None,
+    royalties: vec![],
+    randomness: None,
+    commitment: None,
+    caller_policy: None,
+    auto_start_round: false,
+    auto_distribute: false,
+    jackpot: None,
+    claim_deadline: None,
+    registry: None,
+  }
+}
+
+pub struct Suite {
+  pub app: App,
+  pub game: Addr,
+  pub token: Addr,
+  pub owner: Addr,
+  pub players: Vec<Addr>,
+}
+
+impl Suite {
+  /// Instantiate a game owned by `owner`, with three players funded in
+  /// `DENOM` and in a CW20 token.
+  pub fn new(msg: InstantiateMsg) -> Self {
+    Self::with_token(|_| msg)
+  }
+
+  /// Like `new`, for a game whose config refers to the CW20 token.
+  pub fn with_token(msg: impl FnOnce(&Addr) -> InstantiateMsg) -> Self {
+    Self::build(|_, token| msg(token))
+  }
+
+  /// Like `with_token`, for a game whose config refers to other contracts
+  /// that have to be instantiated first.
+  pub fn build(msg: impl FnOnce(&mut App, &Addr) -> InstantiateMsg) -> Self {
+    let owner = Addr::unchecked("owner");
+    let players = vec![
+      Addr::unchecked("alice"),
+      Addr::unchecked("bob"),
+      Addr::unchecked("carol"),
+    ];
+    let mut app = App::new(|router, _, storage| {
+      for player in players.iter() {
+        router
+          .bank
+          .init_balance(storage, player, coins(INITIAL_BALANCE, DENOM))
+          .unwrap();
+      }
+    });
+    let token_code_id = app.store_code(cw20_token());
+    let token_msg = cw20_base::msg::InstantiateMsg {
+      name: "Token".to_owned(),
+      symbol: "TKN".to_owned(),
+      decimals: 6,
+      initial_balances: players
+        .iter()
+        .map(|player| Cw20Coin {
+          address: player.to_string(),
+          amount: Uint128::new(INITIAL_BALANCE),
+        })
+        .collect(),
+      mint: None,
+      marketing: None,
+    };
+    let token = app
+      .instantiate_contract(token_code_id, owner.clone(), &token_msg, &[], "token", None)
+      .unwrap();
+    let msg = msg(&mut app, &token);
+    let code_id = app.store_code(lottery());
+    let game = app
+      .instantiate_contract(code_id, owner.clone(), &msg, &[], "game", None)
+      .unwrap();
+    Suite {
+      app,
+      game,
+      token,
+      owner,
+      players,
+    }
+  }
+
+  pub fn player(
+    &self,
+    i: usize,
+  ) -> Addr {
+    self.players[i].clone()
+  }
+
+  /// Execute a message on the game, then move on to the next block.
+  pub fn execute(
+    &mut self,
+    sender: &Addr,
+    msg: &ExecuteMsg,
+    funds: &[Coin],
+  ) -> Result<AppResponse, ContractError> {
+    let result = self
+      .app
+      .execute_contract(sender.clone(), self.game.clone(), msg, funds)
+      .map_err(|error| error.downcast::<ContractError>().unwrap());
+    self.next_block();
+    result
+  }
+
+  pub fn next_block(&mut self) {
+    self.app.update_block(|block| {
+      block.height += 1;
+      block.time = block.time.plus_seconds(5);
+    });
+  }
+
+  /// Buy tickets with `DENOM`.
+  pub fn buy(
+    &mut self,
+    player: &Addr,
+    ticket_count: u32,
+  ) -> AppResponse {
+    let msg = ExecuteMsg::BuyTickets {
+      ticket_count,
+      lucky_phrase: None,
+      numbers: None,
+    };
+    let funds = coins(TICKET_PRICE * ticket_count as u128, DENOM);
+    self.execute(player, &msg, &funds).unwrap()
+  }
+
+  /// Send CW20 tokens to the game with an embedded message, from the given
+  /// token contract rather than necessarily the suite's.
+  pub fn send_token(
+    &mut self,
+    token: &Addr,
+    sender: &Addr,
+    amount: u128,
+    msg: &ReceiveMsg,
+  ) -> Result<AppResponse, ContractError> {
+    let send = Cw20ExecuteMsg::Send {
+      contract: self.game.to_string(),
+      amount: Uint128::new(amount),
+      msg: to_binary(msg).unwrap(),
+    };
+    let result = self
+      .app
+      .execute_contract(sender.clone(), token.clone(), &send, &[])
+      .map_err(|error| error.downcast::<ContractError>().unwrap());
+    self.next_block();
+    result
+  }
+
+  /// Buy tickets with the suite's CW20 token.
+  pub fn buy_with_token(
+    &mut self,
+    player: &Addr,
+    ticket_count: u32,
+  ) -> AppResponse {
+    let msg = ReceiveMsg::BuyTickets {
+      ticket_count,
+      lucky_phrase: None,
+      numbers: None,
+    };
+    let token = self.token.clone();
+    let amount = TOKEN_TICKET_PRICE * ticket_count as u128;
+    self.send_token(&token, player, amount, &msg).unwrap()
+  }
+
+  pub fn end_game(&mut self) -> Result<AppResponse, ContractError> {
+    let owner = self.owner.clone();
+    self.execute(&owner, &ExecuteMsg::EndGame { lucky_phrase: None }, &[])
+  }
+
+  pub fn query<T: DeserializeOwned>(
+    &self,
+    msg: &QueryMsg,
+  ) -> T {
+    self.app.wrap().query_wasm_smart(&self.game, msg).unwrap()
+  }
+
+  pub fn balance(
+    &self,
+    addr: &Addr,
+  ) -> u128 {
+    self.app.wrap().query_balance(addr, DENOM).unwrap().amount.u128()
+  }
+
+  pub fn token_balance(
+    &self,
+    addr: &Addr,
+  ) -> u128 {
+    let msg = Cw20QueryMsg::Balance {
+      address: addr.to_string(),
+    };
+    let response: BalanceResponse = self.app.wrap().query_wasm_smart(&self.token, &msg).unwrap();
+    response.balance.u128()
+  }
+}
+
+/// Whether any event of the response has the attribute.
+pub fn has_attribute(
+  response: &AppResponse,
+  key: &str,
+  value: &str,
+) -> bool {
+  response.events.iter().any(|event| {
+    event
+      .attributes
+      .iter()
+      .any(|attr| attr.key == key && attr.value == value)
+  })
+}
+
+/// Errors don't implement `PartialEq`, so compare them by debug output.
+pub fn assert_error(
+  result: Result<AppResponse, ContractError>,
+  expected: ContractError,
+) {
+  let error = result.unwrap_err();
+  assert_eq!(format!("{:?}", error), format!("{:?}", expected));
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use common::{game_msg, Suite, TICKET_PRICE};
+use cosmwasm_std::{Addr, Uint128};
+use cw_lottery::msg::{ExecuteMsg, GetGameResponse, QueryMsg, RandomnessSource};
+use cw_lottery::random::seed::digest;
+use cw_lottery::state::GameStatus;
+
+fn get_game(suite: &Suite) -> GetGameResponse {
+  suite.query(&QueryMsg::GetGame { round: None })
+}
+
+fn end_game_error(suite: &Suite) -> Option<String> {
+  let response = get_game(suite);
+  assert_eq!(response.can_end, response.end_game_error.is_none());
+  response.end_game_error
+}
+
+#[test]
+fn explains_why_active_game_cannot_end() {
+  let mut msg = game_msg();
+  msg.funding_threshold = Some(Uint128::new(3 * TICKET_PRICE));
+  let mut suite = Suite::new(msg);
+  let alice = suite.player(0);
+  let bob = suite.player(1);
+
+  assert_eq!(end_game_error(&suite), Some("NoWinners".to_owned()));
+  suite.buy(&alice, 1);
+  suite.buy(&bob, 1);
+  assert_eq!(end_game_error(&suite), Some("UnderFundingThreshold".to_owned()));
+  suite.buy(&bob, 1);
+  assert_eq!(end_game_error(&suite), None);
+  assert_eq!(get_game(&suite).pots[0].amount, Uint128::new(3 * TICKET_PRICE));
+
+  suite.end_game().unwrap();
+  let response = get_game(&suite);
+  assert_eq!(response.game.status, GameStatus::ENDED);
+  assert_eq!(response.end_game_error, Some("NotActive".to_owned()));
+}
+
+#[test]
+fn explains_why_closed_game_cannot_end() {
+  let secret = "owner's secret";
+  let mut msg = game_msg();
+  msg.randomness = Some(RandomnessSource::CommitReveal {
+    committers: vec![Addr::unchecked("owner")],
+    reveal_timeout_minutes: 60,
+  });
+  msg.commitment = Some(digest(secret));
+  let mut suite = Suite::new(msg);
+  let owner = suite.owner.clone();
+  for i in 0..2 {
+    let player = suite.player(i);
+    suite.buy(&player, 1);
+  }
+
+  suite.end_game().unwrap();
+  assert_eq!(get_game(&suite).game.status, GameStatus::CLOSED);
+  assert_eq!(end_game_error(&suite), Some("AwaitingReveal".to_owned()));
+
+  let reveal = ExecuteMsg::Reveal {
+    secret: secret.to_owned(),
+  };
+  suite.execute(&owner, &reveal, &[]).unwrap();
+  assert_eq!(end_game_error(&suite), None);
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{game_msg, Suite, DENOM};
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_lottery::msg::{CarryOver, ExecuteMsg, GetJackpotResponse, Jackpot, QueryMsg};
+
+const SEEDED: u128 = 500;
+
+/// A game with a jackpot carried over to the given destination, seeded by a
+/// player and with tickets sold to two players.
+fn suite(carry_over: CarryOver) -> Suite {
+  let mut msg = game_msg();
+  msg.jackpot = Some(Jackpot {
+    hit_basis_points: 500,
+    carry_over,
+  });
+  let mut suite = Suite::new(msg);
+  let carol = suite.player(2);
+  suite
+    .execute(&carol, &ExecuteMsg::SeedJackpot {}, &coins(SEEDED, DENOM))
+    .unwrap();
+  for i in 0..2 {
+    let player = suite.player(i);
+    suite.buy(&player, 1);
+  }
+  suite
+}
+
+fn jackpot(suite: &Suite) -> GetJackpotResponse {
+  suite.query(&QueryMsg::GetJackpot { round: None })
+}
+
+#[test]
+fn canceled_jackpot_rolls_into_next_round() {
+  let mut suite = suite(CarryOver::NextRound {});
+  let owner = suite.owner.clone();
+
+  suite.execute(&owner, &ExecuteMsg::CancelGame {}, &[]).unwrap();
+  suite.execute(&owner, &ExecuteMsg::StartRound {}, &[]).unwrap();
+
+  let jackpot = jackpot(&suite);
+  assert_eq!(jackpot.round, 1);
+  assert_eq!(jackpot.amounts[0].amount, Uint128::new(SEEDED));
+}
+
+#[test]
+fn canceled_jackpot_is_sent_to_its_destination() {
+  let treasury = Addr::unchecked("treasury");
+  let mut suite = suite(CarryOver::Address {
+    address: treasury.clone(),
+  });
+  let owner = suite.owner.clone();
+
+  suite.execute(&owner, &ExecuteMsg::CancelGame {}, &[]).unwrap();
+  assert_eq!(suite.balance(&treasury), SEEDED);
+
+  // nothing is left to roll into the next round
+  suite.execute(&owner, &ExecuteMsg::StartRound {}, &[]).unwrap();
+  assert!(jackpot(&suite).amounts[0].amount.is_zero());
+}
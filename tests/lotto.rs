@@ -0,0 +1,97 @@
+mod common;
+
+use common::{game_msg, has_attribute, Suite, DENOM, TICKET_PRICE};
+use cosmwasm_std::{coins, Addr, Uint128};
+use cw_lottery::msg::{
+  ExecuteMsg, GetJackpotResponse, GetWinnersResponse, PrizeTier, QueryMsg, RoyaltyMsg, WinnerSelection,
+};
+use cw_lottery::state::Winner;
+
+/// A lotto game in which every ticket picks both numbers in the range, so that
+/// every ticket matches both drawn numbers whatever the draw.
+fn suite(prize_tiers: Vec<PrizeTier>) -> Suite {
+  let mut msg = game_msg();
+  msg.selection = WinnerSelection::Lotto {
+    numbers_per_ticket: 2,
+    number_range: 2,
+    prize_tiers,
+  };
+  msg.royalties = vec![RoyaltyMsg {
+    address: "treasury".to_owned(),
+    basis_points: 1_000,
+    label: None,
+  }];
+  Suite::new(msg)
+}
+
+fn buy(
+  suite: &mut Suite,
+  player: &Addr,
+  ticket_count: u32,
+) {
+  let msg = ExecuteMsg::BuyTickets {
+    ticket_count,
+    lucky_phrase: None,
+    numbers: Some(vec![vec![1, 2]; ticket_count as usize]),
+  };
+  let funds = coins(TICKET_PRICE * ticket_count as u128, DENOM);
+  suite.execute(player, &msg, &funds).unwrap();
+}
+
+fn winners(suite: &Suite) -> Vec<Winner> {
+  let response: GetWinnersResponse = suite.query(&QueryMsg::GetWinners {
+    round: None,
+    start_after: None,
+    limit: None,
+  });
+  response.winners
+}
+
+/// Jackpot of the round after the current one, once it's started.
+fn next_jackpot(suite: &mut Suite) -> Uint128 {
+  let owner = suite.owner.clone();
+  suite.execute(&owner, &ExecuteMsg::StartRound {}, &[]).unwrap();
+  let jackpot: GetJackpotResponse = suite.query(&QueryMsg::GetJackpot { round: None });
+  jackpot.amounts[0].amount
+}
+
+#[test]
+fn splits_tier_per_ticket_and_carries_dust() {
+  let mut suite = suite(vec![
+    PrizeTier { matches: 2, pct: 45 },
+    PrizeTier { matches: 1, pct: 55 },
+  ]);
+  let alice = suite.player(0);
+  let bob = suite.player(1);
+  buy(&mut suite, &alice, 1);
+  buy(&mut suite, &bob, 2);
+  suite.end_game().unwrap();
+
+  // 270 is left after royalties, of which 45% is 121 split among 3 tickets,
+  // i.e. 40 a ticket, leaving a dust of 1
+  let winners = winners(&suite);
+  assert_eq!(winners.len(), 2);
+  for winner in winners.iter() {
+    assert_eq!(winner.matches, Some(2));
+    let expected = if winner.address == alice { 40 } else { 80 };
+    assert_eq!(winner.claim_amount, Uint128::new(expected));
+  }
+
+  // the dust and the pool of the tier nobody is in carry over
+  assert_eq!(next_jackpot(&mut suite), Uint128::new(270 - 120));
+  assert_eq!(suite.balance(&suite.game), 270);
+}
+
+#[test]
+fn carries_everything_without_winners() {
+  let mut suite = suite(vec![PrizeTier { matches: 0, pct: 100 }]);
+  for i in 0..2 {
+    let player = suite.player(i);
+    buy(&mut suite, &player, 1);
+  }
+  let response = suite.end_game().unwrap();
+  assert!(has_attribute(&response, "winner_count", "0"));
+  assert!(winners(&suite).is_empty());
+
+  assert_eq!(next_jackpot(&mut suite), Uint128::new(180));
+}
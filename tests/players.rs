@@ -0,0 +1,80 @@
+mod common;
+
+use common::{game_msg, Suite};
+use cosmwasm_std::Addr;
+use cw_lottery::msg::{GetPlayersResponse, PlayerOrder, QueryMsg};
+
+/// Bob joins first and ends up with 3 tickets, then carol with 1 and alice
+/// with 2.
+fn suite() -> Suite {
+  let mut suite = Suite::new(game_msg());
+  let alice = suite.player(0);
+  let bob = suite.player(1);
+  let carol = suite.player(2);
+  suite.buy(&bob, 1);
+  suite.buy(&carol, 1);
+  suite.buy(&alice, 2);
+  suite.buy(&bob, 2);
+  suite
+}
+
+/// Page through every player in the given order, two at a time.
+fn page_through(
+  suite: &Suite,
+  order_by: PlayerOrder,
+) -> Vec<(Addr, u32, u32)> {
+  let mut listed = vec![];
+  let mut start_after = None;
+  loop {
+    let response: GetPlayersResponse = suite.query(&QueryMsg::GetPlayers {
+      round: None,
+      order_by: Some(order_by.clone()),
+      start_after,
+      limit: Some(2),
+    });
+    assert!(response.players.len() <= 2);
+    listed.extend(
+      response
+        .players
+        .into_iter()
+        .map(|player| (player.address, player.ticket_count, player.joined_at)),
+    );
+    match response.next {
+      Some(next) => start_after = Some(next),
+      None => return listed,
+    }
+  }
+}
+
+#[test]
+fn pages_by_ticket_count() {
+  let suite = suite();
+  let (alice, bob, carol) = (suite.player(0), suite.player(1), suite.player(2));
+
+  assert_eq!(
+    page_through(&suite, PlayerOrder::TicketCount),
+    vec![(bob, 3, 0), (alice, 2, 2), (carol, 1, 1)]
+  );
+}
+
+#[test]
+fn pages_by_join_order() {
+  let suite = suite();
+  let (alice, bob, carol) = (suite.player(0), suite.player(1), suite.player(2));
+
+  assert_eq!(
+    page_through(&suite, PlayerOrder::JoinOrder),
+    vec![(bob, 3, 0), (carol, 1, 1), (alice, 2, 2)]
+  );
+}
+
+#[test]
+fn pages_by_address() {
+  let suite = suite();
+  let (alice, bob, carol) = (suite.player(0), suite.player(1), suite.player(2));
+
+  assert_eq!(
+    page_through(&suite, PlayerOrder::Address),
+    vec![(alice, 2, 2), (bob, 3, 0), (carol, 1, 1)]
+  );
+}
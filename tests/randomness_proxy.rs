@@ -0,0 +1,308 @@
+use cosmwasm_std::{
+  coins, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, HexBinary, MessageInfo, Response, StdResult, Timestamp,
+  WasmMsg,
+};
+use cw_lottery::contract::{execute, instantiate, query};
+use cw_lottery::error::ContractError;
+use cw_lottery::msg::{
+  ExecuteMsg, GetWinnersResponse, InstantiateMsg, NoisCallback, QueryMsg, RandomnessSource, WinnerSelection,
+};
+use cw_lottery::state::{GameStatus, GAME};
+use cw_multi_test::{App, AppResponse, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const DENOM: &str = "ujuno";
+const RANDOMNESS: &str = "9e8e26615f51552aa3b18b6f0bcf0dae5afbe30321e8d7ea7fa51ebeb1d8fe62";
+
+/// A stand-in for a randomness proxy such as Nois: it records each request and
+/// delivers randomness to the requester on demand.
+mod proxy {
+  use super::*;
+
+  #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+  #[serde(rename_all = "snake_case")]
+  pub enum ExecuteMsg {
+    GetNextRandomness {
+      job_id: String,
+    },
+    // deliver randomness for the pending request, optionally for another job
+    Deliver {
+      job_id: Option<String>,
+      randomness: HexBinary,
+    },
+  }
+
+  #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+  #[serde(rename_all = "snake_case")]
+  enum CallbackMsg {
+    NoisReceive { callback: NoisCallback },
+  }
+
+  const REQUEST: Item<(Addr, String)> = Item::new("request");
+
+  fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+  ) -> StdResult<Response> {
+    match msg {
+      ExecuteMsg::GetNextRandomness { job_id } => {
+        REQUEST.save(deps.storage, &(info.sender, job_id.clone()))?;
+        Ok(Response::new().add_attribute("job_id", job_id))
+      },
+      ExecuteMsg::Deliver { job_id, randomness } => {
+        let (requester, requested_job_id) = REQUEST.load(deps.storage)?;
+        let callback = NoisCallback {
+          job_id: job_id.unwrap_or(requested_job_id),
+          published: env.block.time,
+          randomness,
+        };
+        Ok(Response::new().add_message(WasmMsg::Execute {
+          contract_addr: requester.into(),
+          msg: to_binary(&CallbackMsg::NoisReceive { callback })?,
+          funds: vec![],
+        }))
+      },
+    }
+  }
+
+  fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+  ) -> StdResult<Response> {
+    Ok(Response::new())
+  }
+
+  fn query(
+    _deps: Deps,
+    _env: Env,
+    _msg: Empty,
+  ) -> StdResult<Binary> {
+    to_binary(&Empty {})
+  }
+
+  pub fn contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+  }
+}
+
+fn lottery() -> Box<dyn Contract<Empty>> {
+  Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+struct Suite {
+  app: App,
+  lottery: Addr,
+  proxy: Addr,
+  players: Vec<Addr>,
+}
+
+impl Suite {
+  fn new() -> Self {
+    let owner = Addr::unchecked("owner");
+    let players = vec![Addr::unchecked("alice"), Addr::unchecked("bob")];
+    let mut app = App::new(|router, _, storage| {
+      for player in players.iter() {
+        router.bank.init_balance(storage, player, coins(1_000, DENOM)).unwrap();
+      }
+    });
+
+    let proxy_code_id = app.store_code(proxy::contract());
+    let proxy = app
+      .instantiate_contract(proxy_code_id, owner.clone(), &Empty {}, &[], "proxy", None)
+      .unwrap();
+
+    let lottery_code_id = app.store_code(lottery());
+    let lottery = app
+      .instantiate_contract(
+        lottery_code_id,
+        owner.clone(),
+        &InstantiateMsg {
+          id: "game".to_owned(),
+          owner: None,
+          name: None,
+          duration_minutes: None,
+          denom: DENOM.to_owned(),
+          cw20_token_address: None,
+          ticket_price: "100".to_owned(),
+          extra_prices: vec![],
+          selection: WinnerSelection::Fixed {
+            pct_split: vec![100],
+            winner_count: 1,
+            max_winner_count: None,
+          },
+          has_distinct_winners: true,
+          max_tickets_per_player: None,
+          funding_threshold: None,
+          royalties: vec![],
+          randomness: Some(RandomnessSource::Proxy {
+            address: proxy.clone(),
+            callback_timeout_minutes: 60,
+          }),
+          commitment: None,
+          caller_policy: None,
+          auto_start_round: false,
+          auto_distribute: false,
+          jackpot: None,
+          claim_deadline: None,
+          registry: None,
+        },
+        &[],
+        "lottery",
+        None,
+      )
+      .unwrap();
+
+    Suite {
+      app,
+      lottery,
+      proxy,
+      players,
+    }
+  }
+
+  /// Have every player buy a ticket, then close ticket sales.
+  fn end_game(&mut self) -> AppResponse {
+    for player in self.players.clone().iter() {
+      self
+        .app
+        .execute_contract(
+          player.clone(),
+          self.lottery.clone(),
+          &ExecuteMsg::BuyTickets {
+            ticket_count: 1,
+            lucky_phrase: None,
+            numbers: None,
+          },
+          &coins(100, DENOM),
+        )
+        .unwrap();
+      self.app.update_block(|block| {
+        block.height += 1;
+        block.time = block.time.plus_seconds(5);
+      });
+    }
+    self
+      .app
+      .execute_contract(
+        self.players[0].clone(),
+        self.lottery.clone(),
+        &ExecuteMsg::EndGame { lucky_phrase: None },
+        &[],
+      )
+      .unwrap()
+  }
+
+  fn deliver(
+    &mut self,
+    job_id: Option<String>,
+  ) -> anyhow::Result<AppResponse> {
+    self.app.execute_contract(
+      Addr::unchecked("relayer"),
+      self.proxy.clone(),
+      &proxy::ExecuteMsg::Deliver {
+        job_id,
+        randomness: HexBinary::from_hex(RANDOMNESS).unwrap(),
+      },
+      &[],
+    )
+  }
+
+  fn game_status(&self) -> GameStatus {
+    GAME.query(&self.app.wrap(), self.lottery.clone()).unwrap().status
+  }
+
+  fn winners(&self) -> GetWinnersResponse {
+    self
+      .app
+      .wrap()
+      .query_wasm_smart(
+        &self.lottery,
+        &QueryMsg::GetWinners {
+          round: None,
+          start_after: None,
+          limit: None,
+        },
+      )
+      .unwrap()
+  }
+}
+
+#[test]
+fn end_game_requests_randomness_from_proxy() {
+  let mut suite = Suite::new();
+  let response = suite.end_game();
+
+  let requested = response.events.iter().any(|event| {
+    event.ty == "wasm"
+      && event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "_contract_addr" && attr.value == suite.proxy.as_str())
+      && event
+        .attributes
+        .iter()
+        .any(|attr| attr.key == "job_id" && attr.value == "game/0")
+  });
+  assert!(requested);
+  assert_eq!(suite.game_status(), GameStatus::CLOSED);
+  assert!(suite.winners().winners.is_empty());
+}
+
+#[test]
+fn proxy_callback_draws_winners() {
+  let mut suite = Suite::new();
+  suite.end_game();
+  suite.deliver(None).unwrap();
+
+  assert_eq!(suite.game_status(), GameStatus::ENDED);
+  let winners = suite.winners().winners;
+  assert_eq!(winners.len(), 1);
+  assert!(suite.players.contains(&winners[0].address));
+  assert_eq!(winners[0].claim_amount.u128(), 200);
+}
+
+#[test]
+fn rejects_callback_from_other_sender() {
+  let mut suite = Suite::new();
+  suite.end_game();
+
+  let err = suite
+    .app
+    .execute_contract(
+      Addr::unchecked("mallory"),
+      suite.lottery.clone(),
+      &ExecuteMsg::ReceiveRandomness {
+        callback: NoisCallback {
+          job_id: "game/0".to_owned(),
+          published: Timestamp::from_seconds(0),
+          randomness: HexBinary::from_hex(RANDOMNESS).unwrap(),
+        },
+      },
+      &[],
+    )
+    .unwrap_err();
+  assert!(matches!(
+    err.downcast_ref::<ContractError>(),
+    Some(ContractError::NotAuthorized {})
+  ));
+  assert_eq!(suite.game_status(), GameStatus::CLOSED);
+}
+
+#[test]
+fn rejects_callback_for_other_job() {
+  let mut suite = Suite::new();
+  suite.end_game();
+
+  let err = suite.deliver(Some("game/1".to_owned())).unwrap_err();
+  assert!(matches!(
+    err.root_cause().downcast_ref::<ContractError>(),
+    Some(ContractError::InvalidJobId { job_id }) if job_id == "game/1"
+  ));
+  assert_eq!(suite.game_status(), GameStatus::CLOSED);
+}
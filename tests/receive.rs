@@ -0,0 +1,92 @@
+mod common;
+
+use common::{assert_error, cw20_token, game_msg, Suite, INITIAL_BALANCE, TOKEN_TICKET_PRICE};
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20Coin;
+use cw_lottery::error::ContractError;
+use cw_lottery::msg::{GetTicketCountResponse, QueryMsg, ReceiveMsg};
+use cw_multi_test::Executor;
+
+/// A game whose tickets are priced in the suite's CW20 token.
+fn suite() -> Suite {
+  Suite::with_token(|token| {
+    let mut msg = game_msg();
+    msg.cw20_token_address = Some(token.clone());
+    msg.ticket_price = TOKEN_TICKET_PRICE.to_string();
+    msg
+  })
+}
+
+fn buy_tickets(ticket_count: u32) -> ReceiveMsg {
+  ReceiveMsg::BuyTickets {
+    ticket_count,
+    lucky_phrase: None,
+    numbers: None,
+  }
+}
+
+fn ticket_count(
+  suite: &Suite,
+  addr: &Addr,
+) -> u32 {
+  let msg = QueryMsg::GetPlayerTicketCount {
+    addr: addr.clone(),
+    round: None,
+  };
+  suite.query::<GetTicketCountResponse>(&msg).ticket_count
+}
+
+#[test]
+fn credits_original_sender() {
+  let mut suite = suite();
+  let alice = suite.player(0);
+
+  suite.buy_with_token(&alice, 3);
+  assert_eq!(ticket_count(&suite, &alice), 3);
+  assert_eq!(ticket_count(&suite, &suite.token), 0);
+  assert_eq!(suite.token_balance(&alice), INITIAL_BALANCE - 3 * TOKEN_TICKET_PRICE);
+  assert_eq!(suite.token_balance(&suite.game), 3 * TOKEN_TICKET_PRICE);
+}
+
+#[test]
+fn rejects_wrong_amount() {
+  let mut suite = suite();
+  let alice = suite.player(0);
+  let token = suite.token.clone();
+
+  let result = suite.send_token(&token, &alice, 2 * TOKEN_TICKET_PRICE - 1, &buy_tickets(2));
+  assert_error(result, ContractError::InsufficientFunds {});
+  let result = suite.send_token(&token, &alice, 2 * TOKEN_TICKET_PRICE + 1, &buy_tickets(2));
+  assert_error(result, ContractError::ExcessFunds {});
+
+  // the failed sends were reverted
+  assert_eq!(ticket_count(&suite, &alice), 0);
+  assert_eq!(suite.token_balance(&alice), INITIAL_BALANCE);
+}
+
+#[test]
+fn rejects_wrong_token() {
+  let mut suite = suite();
+  let alice = suite.player(0);
+
+  let code_id = suite.app.store_code(cw20_token());
+  let msg = cw20_base::msg::InstantiateMsg {
+    name: "Other".to_owned(),
+    symbol: "OTHER".to_owned(),
+    decimals: 6,
+    initial_balances: vec![Cw20Coin {
+      address: alice.to_string(),
+      amount: Uint128::new(INITIAL_BALANCE),
+    }],
+    mint: None,
+    marketing: None,
+  };
+  let other = suite
+    .app
+    .instantiate_contract(code_id, suite.owner.clone(), &msg, &[], "other", None)
+    .unwrap();
+
+  let result = suite.send_token(&other, &alice, 2 * TOKEN_TICKET_PRICE, &buy_tickets(2));
+  assert_error(result, ContractError::UnsupportedAsset {});
+  assert_eq!(ticket_count(&suite, &alice), 0);
+}
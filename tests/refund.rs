@@ -0,0 +1,72 @@
+mod common;
+
+use common::{assert_error, game_msg, token_price, Suite, INITIAL_BALANCE, TICKET_PRICE, TOKEN_TICKET_PRICE};
+use cw_lottery::error::ContractError;
+use cw_lottery::msg::ExecuteMsg;
+
+/// A game priced in `DENOM` and in the CW20 token, in which alice pays with
+/// both and bob only with the token.
+fn suite() -> Suite {
+  let mut suite = Suite::with_token(|token| {
+    let mut msg = game_msg();
+    msg.extra_prices = vec![token_price(token)];
+    msg
+  });
+  let alice = suite.player(0);
+  let bob = suite.player(1);
+  suite.buy(&alice, 2);
+  suite.buy_with_token(&alice, 1);
+  suite.buy_with_token(&bob, 3);
+  suite
+}
+
+#[test]
+fn refunds_every_asset_paid_after_cancel() {
+  let mut suite = suite();
+  let owner = suite.owner.clone();
+  let alice = suite.player(0);
+  let bob = suite.player(1);
+  assert_eq!(suite.balance(&alice), INITIAL_BALANCE - 2 * TICKET_PRICE);
+  assert_eq!(suite.token_balance(&alice), INITIAL_BALANCE - TOKEN_TICKET_PRICE);
+
+  let refund = ExecuteMsg::ClaimRefund { round: None };
+  assert_error(suite.execute(&alice, &refund, &[]), ContractError::NotCanceled {});
+
+  suite.execute(&owner, &ExecuteMsg::CancelGame {}, &[]).unwrap();
+  for player in [&alice, &bob] {
+    suite.execute(player, &refund, &[]).unwrap();
+    assert_eq!(suite.balance(player), INITIAL_BALANCE);
+    assert_eq!(suite.token_balance(player), INITIAL_BALANCE);
+  }
+  assert_eq!(suite.balance(&suite.game), 0);
+  assert_eq!(suite.token_balance(&suite.game), 0);
+}
+
+#[test]
+fn rejects_second_refund() {
+  let mut suite = suite();
+  let owner = suite.owner.clone();
+  let alice = suite.player(0);
+  let carol = suite.player(2);
+
+  suite.execute(&owner, &ExecuteMsg::CancelGame {}, &[]).unwrap();
+  let refund = ExecuteMsg::ClaimRefund { round: None };
+  suite.execute(&alice, &refund, &[]).unwrap();
+  assert_error(suite.execute(&alice, &refund, &[]), ContractError::AlreadyRefunded {});
+  assert_eq!(suite.balance(&alice), INITIAL_BALANCE);
+  assert_eq!(suite.token_balance(&alice), INITIAL_BALANCE);
+
+  // nor can anyone who never played claim anything
+  assert_error(suite.execute(&carol, &refund, &[]), ContractError::NotAuthorized {});
+}
+
+#[test]
+fn only_owner_cancels_before_deadline() {
+  let mut suite = suite();
+  let alice = suite.player(0);
+
+  assert_error(
+    suite.execute(&alice, &ExecuteMsg::CancelGame {}, &[]),
+    ContractError::NotAuthorized {},
+  );
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::{assert_error, game_msg, Suite};
+use cosmwasm_std::Addr;
+use cw_lottery::error::ContractError;
+use cw_lottery::msg::{ExecuteMsg, GetGameResponse, GetTicketCountResponse, GetWinnersResponse, QueryMsg};
+use cw_lottery::state::{Game, GameStatus};
+
+fn game(
+  suite: &Suite,
+  round: Option<u64>,
+) -> Game {
+  suite.query::<GetGameResponse>(&QueryMsg::GetGame { round }).game
+}
+
+fn ticket_count(
+  suite: &Suite,
+  addr: &Addr,
+  round: Option<u64>,
+) -> u32 {
+  let msg = QueryMsg::GetPlayerTicketCount {
+    addr: addr.clone(),
+    round,
+  };
+  suite.query::<GetTicketCountResponse>(&msg).ticket_count
+}
+
+fn winner_count(
+  suite: &Suite,
+  round: Option<u64>,
+) -> usize {
+  let msg = QueryMsg::GetWinners {
+    round,
+    start_after: None,
+    limit: None,
+  };
+  suite.query::<GetWinnersResponse>(&msg).winners.len()
+}
+
+#[test]
+fn archives_ended_round() {
+  let mut suite = Suite::new(game_msg());
+  let owner = suite.owner.clone();
+  let alice = suite.player(0);
+  let bob = suite.player(1);
+  let carol = suite.player(2);
+
+  suite.buy(&alice, 1);
+  suite.buy(&bob, 2);
+  assert_error(
+    suite.execute(&owner, &ExecuteMsg::StartRound {}, &[]),
+    ContractError::RoundInProgress {},
+  );
+  suite.end_game().unwrap();
+  suite.execute(&owner, &ExecuteMsg::StartRound {}, &[]).unwrap();
+  suite.buy(&carol, 4);
+
+  // the current round is the default
+  let current = game(&suite, None);
+  assert_eq!(current.round, 1);
+  assert_eq!(current.status, GameStatus::ACTIVE);
+  assert_eq!(current.ticket_count, 4);
+  assert_eq!(ticket_count(&suite, &carol, None), 4);
+  assert_eq!(ticket_count(&suite, &alice, None), 0);
+  assert_eq!(winner_count(&suite, None), 0);
+
+  // while the past round is kept as it ended
+  let archived = game(&suite, Some(0));
+  assert_eq!(archived.round, 0);
+  assert_eq!(archived.status, GameStatus::ENDED);
+  assert_eq!(archived.ticket_count, 3);
+  assert_eq!(ticket_count(&suite, &alice, Some(0)), 1);
+  assert_eq!(ticket_count(&suite, &bob, Some(0)), 2);
+  assert_eq!(ticket_count(&suite, &carol, Some(0)), 0);
+  assert_eq!(winner_count(&suite, Some(0)), 1);
+  assert_eq!(game(&suite, Some(1)), current);
+}
+
+#[test]
+fn starts_next_round_automatically() {
+  let mut msg = game_msg();
+  msg.auto_start_round = true;
+  let mut suite = Suite::new(msg);
+  for i in 0..2 {
+    let player = suite.player(i);
+    suite.buy(&player, 1);
+  }
+
+  suite.end_game().unwrap();
+  assert_eq!(game(&suite, None).round, 1);
+  assert_eq!(game(&suite, None).status, GameStatus::ACTIVE);
+  assert_eq!(game(&suite, Some(0)).status, GameStatus::ENDED);
+  assert_eq!(winner_count(&suite, Some(0)), 1);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use common::{game_msg, token_price, Suite, INITIAL_BALANCE, TICKET_PRICE, TOKEN_TICKET_PRICE};
+use cosmwasm_std::{Addr, Uint128};
+use cw_lottery::asset::Asset;
+use cw_lottery::msg::{ExecuteMsg, GetWinnersResponse, QueryMsg, RoyaltyMsg, WinnerSelection};
+
+/// Two winners split 60/40 whatever is left of each pot after a 10% royalty.
+/// Alice pays 300 in `DENOM` and bob 100 in the CW20 token.
+fn suite() -> Suite {
+  let mut suite = Suite::with_token(|token| {
+    let mut msg = game_msg();
+    msg.extra_prices = vec![token_price(token)];
+    msg.selection = WinnerSelection::Fixed {
+      pct_split: vec![60, 40],
+      winner_count: 2,
+      max_winner_count: None,
+    };
+    msg.royalties = vec![RoyaltyMsg {
+      address: "treasury".to_owned(),
+      basis_points: 1_000,
+      label: None,
+    }];
+    msg
+  });
+  let alice = suite.player(0);
+  let bob = suite.player(1);
+  suite.buy(&alice, 3);
+  suite.buy_with_token(&bob, 2);
+  suite
+}
+
+#[test]
+fn pays_royalties_and_prizes_in_every_asset() {
+  let mut suite = suite();
+  let alice = suite.player(0);
+  let treasury = Addr::unchecked("treasury");
+
+  suite.end_game().unwrap();
+  assert_eq!(suite.balance(&treasury), 30);
+  assert_eq!(suite.token_balance(&treasury), 10);
+
+  let response: GetWinnersResponse = suite.query(&QueryMsg::GetWinners {
+    round: None,
+    start_after: None,
+    limit: None,
+  });
+  let winners = response.winners;
+  assert_eq!(winners.len(), 2);
+
+  // each position's share of every pot
+  let shares = [(162, 54), (108, 36)];
+  for (winner, (native, tokens)) in winners.iter().zip(shares) {
+    assert_eq!(winner.claim_amount, Uint128::new(native));
+    assert_eq!(winner.extra_claim_amounts.len(), 1);
+    assert_eq!(
+      winner.extra_claim_amounts[0].asset,
+      Asset::Cw20 {
+        address: suite.token.clone()
+      }
+    );
+    assert_eq!(winner.extra_claim_amounts[0].amount, Uint128::new(tokens));
+
+    let claim = ExecuteMsg::ClaimPrize {
+      positions: None,
+      round: None,
+    };
+    suite.execute(&winner.address, &claim, &[]).unwrap();
+
+    let (paid, paid_in_tokens) = if winner.address == alice {
+      (3 * TICKET_PRICE, 0)
+    } else {
+      (0, 2 * TOKEN_TICKET_PRICE)
+    };
+    assert_eq!(suite.balance(&winner.address), INITIAL_BALANCE - paid + native);
+    assert_eq!(
+      suite.token_balance(&winner.address),
+      INITIAL_BALANCE - paid_in_tokens + tokens
+    );
+  }
+
+  // every pot has been paid out in full
+  assert_eq!(suite.balance(&suite.game), 0);
+  assert_eq!(suite.token_balance(&suite.game), 0);
+}
@@ -0,0 +1,77 @@
+mod common;
+
+use common::{game_msg, Suite};
+use cosmwasm_std::{Addr, Uint128};
+use cw_lottery::msg::{
+  GetGameResponse, GetWinnersResponse, QueryMsg, RoyaltyMsg, SimulateEndGameResponse, WinnerSelection,
+};
+use cw_lottery::state::GameStatus;
+
+/// Two winners split 60/40 whatever is left of the pot after a 10% royalty.
+fn suite() -> Suite {
+  let mut msg = game_msg();
+  msg.selection = WinnerSelection::Fixed {
+    pct_split: vec![60, 40],
+    winner_count: 2,
+    max_winner_count: None,
+  };
+  msg.royalties = vec![RoyaltyMsg {
+    address: "treasury".to_owned(),
+    basis_points: 1_000,
+    label: Some("treasury".to_owned()),
+  }];
+  Suite::new(msg)
+}
+
+fn simulate(suite: &Suite) -> SimulateEndGameResponse {
+  suite.query(&QueryMsg::SimulateEndGame {})
+}
+
+#[test]
+fn previews_payouts_of_end_game() {
+  let mut suite = suite();
+  let treasury = Addr::unchecked("treasury");
+  for (i, ticket_count) in [1, 2, 2].iter().enumerate() {
+    let player = suite.player(i);
+    suite.buy(&player, *ticket_count);
+  }
+
+  let simulation = simulate(&suite);
+  assert_eq!(simulation.pots[0].amount, Uint128::new(500));
+  assert_eq!(simulation.royalties.len(), 1);
+  assert_eq!(simulation.royalties[0].address, treasury);
+  assert_eq!(simulation.royalties[0].amounts[0].amount, Uint128::new(50));
+  assert_eq!(simulation.winnings[0].amount, Uint128::new(450));
+  assert_eq!(simulation.winner_count, 2);
+  let claim_amounts: Vec<Uint128> = simulation.positions.iter().map(|payout| payout.claim_amount).collect();
+  assert_eq!(claim_amounts, vec![Uint128::new(270), Uint128::new(180)]);
+  assert!(simulation.unallocated[0].amount.is_zero());
+  assert_eq!(simulation.refund_to, None);
+
+  // simulating leaves the game as it was
+  let game: GetGameResponse = suite.query(&QueryMsg::GetGame { round: None });
+  assert_eq!(game.game.status, GameStatus::ACTIVE);
+
+  // and ending it pays out what was simulated
+  suite.end_game().unwrap();
+  assert_eq!(suite.balance(&treasury), 50);
+  let response: GetWinnersResponse = suite.query(&QueryMsg::GetWinners {
+    round: None,
+    start_after: None,
+    limit: None,
+  });
+  let paid: Vec<Uint128> = response.winners.iter().map(|winner| winner.claim_amount).collect();
+  assert_eq!(paid, claim_amounts);
+}
+
+#[test]
+fn previews_refund_of_sole_player() {
+  let mut suite = suite();
+  let alice = suite.player(0);
+  suite.buy(&alice, 3);
+
+  let simulation = simulate(&suite);
+  assert_eq!(simulation.refund_to, Some(alice));
+  assert!(simulation.royalties.is_empty());
+  assert!(simulation.positions.is_empty());
+}
@@ -0,0 +1,166 @@
+mod common;
+
+use common::{assert_error, game_msg, lottery, Suite, INITIAL_BALANCE, TICKET_PRICE};
+use cosmwasm_std::{Addr, Uint128};
+use cw_lottery::error::ContractError;
+use cw_lottery::msg::{
+  CarryOver, ClaimDeadline, ExecuteMsg, GetJackpotResponse, GetWinnersResponse, InstantiateMsg, Jackpot, QueryMsg,
+};
+use cw_lottery::state::Winner;
+use cw_multi_test::{AppResponse, Executor};
+
+const DEADLINE_MINUTES: u32 = 60;
+const PRIZE: u128 = 2 * TICKET_PRICE;
+
+/// A game whose winner has an hour to claim their prize once it ends, before
+/// it may be swept to the given destination.
+fn game_msg_sweeping_to(sweep_to: CarryOver) -> InstantiateMsg {
+  let mut msg = game_msg();
+  msg.claim_deadline = Some(ClaimDeadline {
+    minutes: DEADLINE_MINUTES,
+    sweep_to,
+  });
+  msg
+}
+
+/// End a game with tickets sold to two players, whose sole winner wins the
+/// whole pot.
+fn end_game(suite: &mut Suite) {
+  for i in 0..2 {
+    let player = suite.player(i);
+    suite.buy(&player, 1);
+  }
+  suite.end_game().unwrap();
+}
+
+fn suite(sweep_to: CarryOver) -> Suite {
+  let mut suite = Suite::new(game_msg_sweeping_to(sweep_to));
+  end_game(&mut suite);
+  suite
+}
+
+fn pass_deadline(suite: &mut Suite) {
+  suite.app.update_block(|block| {
+    block.time = block.time.plus_seconds(60 * DEADLINE_MINUTES as u64 + 1);
+  });
+}
+
+fn sweep(suite: &mut Suite) -> Result<AppResponse, ContractError> {
+  let owner = suite.owner.clone();
+  suite.execute(&owner, &ExecuteMsg::SweepUnclaimed { round: None }, &[])
+}
+
+fn winner(suite: &Suite) -> Winner {
+  let response: GetWinnersResponse = suite.query(&QueryMsg::GetWinners {
+    round: None,
+    start_after: None,
+    limit: None,
+  });
+  response.winners[0].clone()
+}
+
+#[test]
+fn sweeps_only_after_deadline() {
+  let treasury = Addr::unchecked("treasury");
+  let mut suite = suite(CarryOver::Address {
+    address: treasury.clone(),
+  });
+
+  assert_error(sweep(&mut suite), ContractError::ClaimDeadlineNotReached {});
+  pass_deadline(&mut suite);
+  sweep(&mut suite).unwrap();
+  assert_eq!(suite.balance(&treasury), PRIZE);
+
+  // the winner can no longer claim what was swept
+  let winner = winner(&suite);
+  assert!(winner.is_swept);
+  let balance = suite.balance(&winner.address);
+  let claim_all = ExecuteMsg::ClaimPrize {
+    positions: None,
+    round: None,
+  };
+  assert_error(
+    suite.execute(&winner.address, &claim_all, &[]),
+    ContractError::NothingToClaim {},
+  );
+  let claim_position = ExecuteMsg::ClaimPrize {
+    positions: Some(vec![0]),
+    round: None,
+  };
+  suite.execute(&winner.address, &claim_position, &[]).unwrap();
+  assert_eq!(suite.balance(&winner.address), balance);
+
+  assert_error(sweep(&mut suite), ContractError::NothingToSweep {});
+}
+
+#[test]
+fn leaves_claimed_prizes_alone() {
+  let mut suite = suite(CarryOver::Address {
+    address: Addr::unchecked("treasury"),
+  });
+  let winner = winner(&suite);
+  let claim = ExecuteMsg::ClaimPrize {
+    positions: None,
+    round: None,
+  };
+  suite.execute(&winner.address, &claim, &[]).unwrap();
+
+  pass_deadline(&mut suite);
+  assert_error(sweep(&mut suite), ContractError::NothingToSweep {});
+  assert_eq!(suite.balance(&winner.address), INITIAL_BALANCE - TICKET_PRICE + PRIZE);
+}
+
+#[test]
+fn sweeps_to_next_round() {
+  // only games paying out a jackpot may sweep into the next round's; this one
+  // always hits, so that the winner keeps their prize until it's swept
+  let mut msg = game_msg_sweeping_to(CarryOver::NextRound {});
+  msg.jackpot = Some(Jackpot {
+    hit_basis_points: 10_000,
+    carry_over: CarryOver::NextRound {},
+  });
+  let mut suite = Suite::new(msg);
+  end_game(&mut suite);
+  let owner = suite.owner.clone();
+
+  pass_deadline(&mut suite);
+  sweep(&mut suite).unwrap();
+  suite.execute(&owner, &ExecuteMsg::StartRound {}, &[]).unwrap();
+
+  let jackpot: GetJackpotResponse = suite.query(&QueryMsg::GetJackpot { round: None });
+  assert_eq!(jackpot.round, 1);
+  assert_eq!(jackpot.amounts[0].amount, Uint128::new(PRIZE));
+}
+
+#[test]
+fn sweeps_to_another_lottery() {
+  let mut other = None;
+  let mut suite = Suite::build(|app, _| {
+    // a lottery with a jackpot for the sweep to seed
+    let mut other_msg = game_msg();
+    other_msg.id = "other".to_owned();
+    other_msg.jackpot = Some(Jackpot {
+      hit_basis_points: 500,
+      carry_over: CarryOver::NextRound {},
+    });
+    let code_id = app.store_code(lottery());
+    let address = app
+      .instantiate_contract(code_id, Addr::unchecked("owner"), &other_msg, &[], "other", None)
+      .unwrap();
+    other = Some(address.clone());
+    game_msg_sweeping_to(CarryOver::Lottery { address })
+  });
+  let other = other.unwrap();
+  end_game(&mut suite);
+
+  pass_deadline(&mut suite);
+  sweep(&mut suite).unwrap();
+
+  let jackpot: GetJackpotResponse = suite
+    .app
+    .wrap()
+    .query_wasm_smart(&other, &QueryMsg::GetJackpot { round: None })
+    .unwrap();
+  assert_eq!(jackpot.amounts[0].amount, Uint128::new(PRIZE));
+  assert_eq!(suite.balance(&other), PRIZE);
+}